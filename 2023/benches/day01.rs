@@ -0,0 +1,65 @@
+//! Day 1, scanning whole documents against going line by line.
+//!
+//! `cargo bench --bench day01`. Benchmarks build without debug assertions, so these
+//! run on the real inputs.
+
+use aoc2023::{
+    day01::{
+        matcher::Matcher,
+        part1, scan,
+        words::{DIGITS, ENGLISH},
+    },
+    Challenge,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// How part 1 used to go about it: every character of every line.
+#[allow(clippy::double_ended_iterator_last)]
+fn part1_lines(document: &str) -> u32 {
+    document
+        .lines()
+        .map(|line| {
+            let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+            let first = digits.next().unwrap();
+            let last = digits.last().unwrap_or(first);
+
+            first * 10 + last
+        })
+        .sum()
+}
+
+fn part1(c: &mut Criterion) {
+    let document = part1::Part::input();
+    let mut group = c.benchmark_group("day01/part1");
+
+    group.bench_function("lines", |b| b.iter(|| part1_lines(black_box(document))));
+    group.bench_function("scan", |b| {
+        b.iter(|| scan::total(black_box(document.as_bytes())))
+    });
+
+    group.finish();
+}
+
+fn part2(c: &mut Criterion) {
+    let document = part1::Part::input();
+    let matcher = Matcher::new(DIGITS.into_iter().chain(ENGLISH));
+    let mut group = c.benchmark_group("day01/part2");
+
+    group.bench_function("build", |b| {
+        b.iter(|| Matcher::new(black_box(DIGITS.into_iter().chain(ENGLISH))))
+    });
+    group.bench_function("lines", |b| {
+        b.iter(|| {
+            black_box(document)
+                .lines()
+                .map(|line| matcher.calibration(line).unwrap())
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("scan", |b| b.iter(|| matcher.total(black_box(document))));
+
+    group.finish();
+}
+
+criterion_group!(benches, part1, part2);
+criterion_main!(benches);
@@ -0,0 +1,142 @@
+//! Day 8, walking interned ids against hashing labels at every step.
+//!
+//! `cargo bench --bench day08`. Benchmarks build without debug assertions, so these
+//! run on the real inputs.
+
+use ahash::AHashMap;
+use aoc2023::{
+    day08::{
+        network::{is_end, Network},
+        part1,
+    },
+    Challenge,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// How labels used to be keyed: their three bytes, packed into a `u32`.
+fn hash(label: &str) -> u32 {
+    let mut bytes = [0; 4];
+    bytes[1..].copy_from_slice(label.as_bytes());
+    u32::from_le_bytes(bytes)
+}
+
+/// A network the way it used to be parsed: instructions as they are, a hash map from
+/// packed labels to both successors, and the ghosts' starts.
+struct Hashed<'a> {
+    instructions: &'a str,
+    map: AHashMap<u32, (&'a str, &'a str)>,
+    starts: Vec<&'a str>,
+}
+
+fn parse_hashed(input: &str) -> Hashed<'_> {
+    let (instructions, nodes) = input.split_once("\n\n").unwrap();
+    let mut map = AHashMap::new();
+    let mut starts = Vec::new();
+
+    for line in nodes.lines() {
+        let (node, targets) = line.split_once(" = ").unwrap();
+        let (left, right) = targets
+            .strip_prefix('(')
+            .unwrap()
+            .strip_suffix(')')
+            .unwrap()
+            .split_once(", ")
+            .unwrap();
+
+        if node.ends_with('A') {
+            starts.push(node);
+        }
+
+        map.insert(hash(node), (left, right));
+    }
+
+    Hashed {
+        instructions,
+        map,
+        starts,
+    }
+}
+
+/// Steps from `start` to the first node `end` accepts, hashing at every step.
+fn walk_hashed(network: &Hashed, start: &str, end: impl Fn(&str) -> bool) -> usize {
+    let mut node = start;
+
+    for (n, instruction) in network.instructions.chars().cycle().enumerate() {
+        if end(node) {
+            return n;
+        }
+
+        let (left, right) = network.map[&hash(node)];
+        node = match instruction {
+            'L' => left,
+            _ => right,
+        };
+    }
+
+    unreachable!("walks are infinite")
+}
+
+fn part1(c: &mut Criterion) {
+    let input = part1::Part::input();
+    let mut group = c.benchmark_group("day08/part1");
+
+    group.bench_function("hashed", |b| {
+        b.iter(|| {
+            let network = parse_hashed(black_box(input));
+            walk_hashed(&network, "AAA", |node| node == "ZZZ")
+        })
+    });
+    group.bench_function("interned", |b| {
+        b.iter(|| {
+            let network = Network::parse(black_box(input)).unwrap();
+            let target = network.id("ZZZ").unwrap();
+
+            let n = network
+                .walk_ids(network.id("AAA").unwrap())
+                .position(|node| node == target);
+
+            n.unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+/// Every ghost to its first `Z` node, the walks the LCM is taken of.
+fn part2(c: &mut Criterion) {
+    let input = part1::Part::input();
+    let mut group = c.benchmark_group("day08/part2");
+
+    group.bench_function("hashed", |b| {
+        b.iter(|| {
+            let network = parse_hashed(black_box(input));
+
+            network
+                .starts
+                .iter()
+                .map(|start| walk_hashed(&network, start, is_end))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("interned", |b| {
+        b.iter(|| {
+            let network = Network::parse(black_box(input)).unwrap();
+
+            network
+                .starts()
+                .into_iter()
+                .map(|start| {
+                    network
+                        .walk_ids(network.id(start).unwrap())
+                        .position(|node| is_end(network.label(node)))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, part1, part2);
+criterion_main!(benches);
@@ -0,0 +1,437 @@
+use itertools::Either;
+use memchr::memchr;
+
+/// A word found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub value: u32,
+    /// Byte offset into the line.
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Finds the first and last of a set of words in a line, each standing for a value.
+///
+/// Words may overlap: in `eightwo`, `eight` comes first and `two` last. If one word
+/// contains another (`seventeen` and `seven`), the longer one wins where both start
+/// (first) or end (last) at the same spot.
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Matcher {
+    /// Panics if any word is empty. If a word appears twice, the later value wins.
+    pub fn new<'a>(words: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        let words = words.into_iter().collect::<Vec<_>>();
+
+        Self {
+            forward: Automaton::new(&words, false),
+            backward: Automaton::new(&words, true),
+        }
+    }
+
+    /// The word starting first, ties going to the longest.
+    pub fn first(&self, line: &str) -> Option<Match> {
+        self.forward.find(line.bytes())
+    }
+
+    /// The word ending last, ties going to the longest.
+    pub fn last(&self, line: &str) -> Option<Match> {
+        // Found on the reversed line, where `start` is counted from the end.
+        let found = self.backward.find(line.bytes().rev())?;
+
+        Some(Match {
+            start: line.len() - found.start - found.len,
+            ..found
+        })
+    }
+
+    /// The first value, followed by the last one, as in `12` and `3` making `123`. Both
+    /// are the same for lines with just one word.
+    ///
+    /// `None` if the line has no words, or the value doesn't fit.
+    pub fn calibration(&self, line: &str) -> Option<u32> {
+        concat(self.first(line)?.value, self.last(line)?.value)
+    }
+
+    /// Sum of the calibration values of all lines. `None` if any line has none, or the
+    /// sum doesn't fit.
+    ///
+    /// Like [`scan::total`](super::scan::total), lines are only read up to their first
+    /// word, and back from their end to their last one. `memchr` skips over the rest.
+    pub fn total(&self, document: &str) -> Option<u32> {
+        let mut rest = document.as_bytes();
+        let mut sum = 0_u32;
+
+        while !rest.is_empty() {
+            let first = self
+                .forward
+                .find(rest.iter().copied().take_while(|&b| b != b'\n'))?;
+            let after = first.start + first.len;
+            let end = memchr(b'\n', &rest[after..]).map_or(rest.len(), |end| after + end);
+
+            // Can't run into the previous line: there's a word before that.
+            let last = self.backward.find(rest[..end].iter().rev().copied())?;
+
+            sum = sum.checked_add(concat(first.value, last.value)?)?;
+            rest = rest.get(end + 1..).unwrap_or_default();
+        }
+
+        Some(sum)
+    }
+}
+
+/// `first`, followed by the digits of `last`.
+fn concat(first: u32, last: u32) -> Option<u32> {
+    // Digits mostly: no need to count them.
+    let shift = if last < 10 {
+        10
+    } else {
+        10_u32.checked_pow(last.ilog10() + 1)?
+    };
+
+    first.checked_mul(shift)?.checked_add(last)
+}
+
+/// Aho-Corasick automaton, with failure links folded into a full transition table, so
+/// every byte is exactly one lookup.
+///
+/// Bytes not in any word share a single class, which keeps the table to a few KB.
+/// Transitions hold the next state's offset into the table rather than its id, and flag
+/// states where words end: no multiplying, and no looking at `outputs` for most bytes.
+#[derive(Debug, Clone)]
+struct Automaton {
+    classes: [u8; 256],
+    n_classes: usize,
+    /// `n_classes` transitions per state, state 0 being the root.
+    transitions: Vec<u32>,
+    /// Per state, the longest word ending there, as `(value, len)`.
+    outputs: Vec<Option<(u32, usize)>>,
+    /// How far past the start of a match to keep looking for one starting earlier. Only
+    /// needed if some word contains another.
+    horizon: usize,
+}
+
+impl Automaton {
+    const NONE: u32 = u32::MAX;
+    const OUTPUT: u32 = 1 << 31;
+
+    /// Matches `words`, or with `reversed`, the words spelled backwards.
+    fn new<'a>(words: &[(&'a str, u32)], reversed: bool) -> Self {
+        let bytes = |word: &'a str| {
+            if reversed {
+                Either::Right(word.bytes().rev())
+            } else {
+                Either::Left(word.bytes())
+            }
+        };
+
+        let mut classes = [0; 256];
+        let mut n_classes = 1;
+        for &(word, _) in words {
+            for b in word.bytes() {
+                if classes[b as usize] == 0 {
+                    classes[b as usize] = n_classes as u8;
+                    n_classes += 1;
+                }
+            }
+        }
+
+        // Trie first, missing edges marked as such.
+        let max_states = words.iter().map(|(word, _)| word.len()).sum::<usize>() + 1;
+        let mut transitions = Vec::with_capacity(max_states * n_classes);
+        let mut outputs = Vec::with_capacity(max_states);
+        transitions.resize(n_classes, Self::NONE);
+        outputs.push(None);
+
+        for &(word, value) in words {
+            assert!(!word.is_empty(), "empty words can't be matched");
+
+            let mut state = 0;
+            for b in bytes(word) {
+                let edge = state * n_classes + classes[b as usize] as usize;
+                if transitions[edge] == Self::NONE {
+                    transitions[edge] = outputs.len() as u32;
+                    transitions.resize(transitions.len() + n_classes, Self::NONE);
+                    outputs.push(None);
+                }
+                state = transitions[edge] as usize;
+            }
+
+            outputs[state] = Some((value, word.len()));
+        }
+
+        // Breadth-first, so failure links always point at finished states. Missing edges
+        // become those of the failure state: the longest suffix that's still a prefix.
+        let mut failure = vec![0; outputs.len()];
+        let mut queue = Vec::with_capacity(outputs.len());
+        queue.push(0);
+        let mut i = 0;
+
+        while let Some(&state) = queue.get(i) {
+            i += 1;
+
+            for class in 0..n_classes {
+                let edge = state * n_classes + class;
+                let fallback = if state == 0 {
+                    0
+                } else {
+                    transitions[failure[state] * n_classes + class]
+                };
+
+                match transitions[edge] {
+                    Self::NONE => transitions[edge] = fallback,
+                    next => {
+                        let next = next as usize;
+                        failure[next] = fallback as usize;
+                        // A word ending here is always longer than one ending at the
+                        // failure state.
+                        outputs[next] = outputs[next].or(outputs[fallback as usize]);
+                        queue.push(next);
+                    }
+                }
+            }
+        }
+
+        // A word ending partway through another: the other one might start earlier, or
+        // at the same spot but be longer. Words ending other words are found along with
+        // them, so don't count.
+        let nested = words.iter().any(|&(word, _)| {
+            let mut state = 0;
+            bytes(word).take(word.len() - 1).any(|b| {
+                state = transitions[state * n_classes + classes[b as usize] as usize] as usize;
+                outputs[state].is_some()
+            })
+        });
+        let horizon = if nested {
+            words.iter().map(|(word, _)| word.len()).max().unwrap_or(0)
+        } else {
+            0
+        };
+
+        for next in &mut transitions {
+            let state = *next as usize;
+            *next = (state * n_classes) as u32;
+            if outputs[state].is_some() {
+                *next |= Self::OUTPUT;
+            }
+        }
+
+        Self {
+            classes,
+            n_classes,
+            transitions,
+            outputs,
+            horizon,
+        }
+    }
+
+    /// The match starting first, ties going to the longest.
+    fn find(&self, bytes: impl Iterator<Item = u8>) -> Option<Match> {
+        let mut state = 0;
+        let mut best: Option<Match> = None;
+
+        for (i, b) in bytes.enumerate() {
+            // Matches are found by where they end. Once past the longest word, nothing
+            // can start before the best one anymore.
+            if best.is_some_and(|best| i >= best.start + self.horizon) {
+                break;
+            }
+
+            let next = self.transitions[state + self.classes[b as usize] as usize];
+            state = (next & !Self::OUTPUT) as usize;
+
+            if next & Self::OUTPUT != 0 {
+                let (value, len) = self.outputs[state / self.n_classes].unwrap();
+                let start = i + 1 - len;
+
+                // Without nested words, the first match found is it.
+                if self.horizon == 0 {
+                    return Some(Match { value, start, len });
+                }
+
+                // Same start, but found later: that's a longer word.
+                if best.is_none_or(|best| start <= best.start) {
+                    best = Some(Match { value, start, len });
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::words::{DIGITS, ENGLISH, ENGLISH_EXTENDED, FRENCH, GERMAN};
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    fn english() -> Matcher {
+        Matcher::new(DIGITS.into_iter().chain(ENGLISH))
+    }
+
+    #[rstest]
+    #[case("two1nine", 29)]
+    #[case("eightwothree", 83)]
+    #[case("abcone2threexyz", 13)]
+    #[case("xtwone3four", 24)]
+    #[case("4nineeightseven2", 42)]
+    #[case("zoneight234", 14)]
+    #[case("7pqrstsixteen", 76)]
+    #[case("eightwo", 82)]
+    #[case("oneight", 18)]
+    #[case("sevenine", 79)]
+    #[case("nineight", 98)]
+    #[case("twone", 21)]
+    #[case("ffive", 55)]
+    #[case("ninine", 99)]
+    #[case("sesevenn", 77)]
+    #[case("thrthreee", 33)]
+    fn test_calibration(#[case] line: &str, #[case] expected: u32) {
+        assert_eq!(english().calibration(line), Some(expected));
+    }
+
+    #[test]
+    fn test_positions() {
+        let matcher = english();
+
+        assert_eq!(
+            matcher.first("xeightwo"),
+            Some(Match {
+                value: 8,
+                start: 1,
+                len: 5
+            })
+        );
+        assert_eq!(
+            matcher.last("xeightwo"),
+            Some(Match {
+                value: 2,
+                start: 5,
+                len: 3
+            })
+        );
+        assert_eq!(matcher.first("abc"), None);
+        assert_eq!(matcher.last(""), None);
+    }
+
+    #[rstest]
+    #[case(&ENGLISH_EXTENDED, "7pqrstsixteen", 716)]
+    #[case(&ENGLISH_EXTENDED, "twelveightwo", 122)]
+    #[case(&ENGLISH_EXTENDED, "seventeenineteen", 1719)]
+    #[case(&ENGLISH_EXTENDED, "zerofive", 5)]
+    #[case(&ENGLISH_EXTENDED, "fivezero", 50)]
+    #[case(&ENGLISH_EXTENDED, "0x0", 0)]
+    #[case(&GERMAN, "zweiundzwanzig", 22)]
+    #[case(&GERMAN, "dreizehn", 310)]
+    #[case(&GERMAN, "xfünfachtelf", 511)]
+    #[case(&FRENCH, "zéroneuf", 9)]
+    #[case(&FRENCH, "quatrevingtdix", 410)]
+    #[case(&FRENCH, "sixtroisept", 67)]
+    fn test_languages(#[case] table: &[(&str, u32)], #[case] line: &str, #[case] expected: u32) {
+        let matcher = Matcher::new(
+            DIGITS
+                .into_iter()
+                .chain(ENGLISH)
+                .chain(table.iter().copied()),
+        );
+
+        assert_eq!(matcher.calibration(line), Some(expected));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let matcher = Matcher::new([("big", 40_000), ("bigger", 100_000), ("huge", u32::MAX)]);
+
+        assert_eq!(matcher.calibration("big"), Some(4_000_040_000));
+        assert_eq!(matcher.calibration("bigger"), None);
+        assert_eq!(matcher.calibration("huge"), None);
+        assert_eq!(matcher.total("big\nbig"), None);
+    }
+
+    #[test]
+    fn test_total() {
+        let matcher = english();
+
+        assert_eq!(matcher.total(include_str!("input/sample/2.txt")), Some(281));
+        assert_eq!(matcher.total("one\nnothing"), None);
+        assert_eq!(matcher.total(""), Some(0));
+        assert_eq!(matcher.total("one\n\ntwo"), None);
+        assert_eq!(matcher.total("eightwo\r\nnine\r\n"), Some(181));
+    }
+
+    #[test]
+    fn test_nested() {
+        let matcher = Matcher::new([("seven", 7), ("seventeen", 17), ("teen", 10), ("een", 0)]);
+
+        // Same start, or same end: the longest wins.
+        assert_eq!(matcher.first("xseventeen").map(|m| m.value), Some(17));
+        assert_eq!(matcher.last("seventeenx").map(|m| m.value), Some(17));
+        // Otherwise, starting first and ending last is all that counts.
+        assert_eq!(matcher.first("sevenxteen").map(|m| m.value), Some(7));
+        assert_eq!(matcher.last("sevenxteen").map(|m| m.value), Some(10));
+        assert_eq!(matcher.last("teeen").map(|m| m.value), Some(0));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let matcher = Matcher::new([("one", 1), ("one", 11)]);
+
+        assert_eq!(matcher.calibration("one"), Some(1111));
+    }
+
+    /// Every occurrence of every word, by `(start, len)`.
+    fn occurrences(words: &[(String, u32)], line: &str) -> Vec<Match> {
+        let mut found = Vec::new();
+
+        for (word, value) in words {
+            for start in 0..line.len() {
+                if line[start..].starts_with(word.as_str()) {
+                    found.push(Match {
+                        value: *value,
+                        start,
+                        len: word.len(),
+                    });
+                }
+            }
+        }
+
+        found
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(
+            words in prop::collection::vec("[ab]{1,4}", 1..6),
+            line in "[abc]{0,20}",
+        ) {
+            // Last value wins for duplicates, as in the matcher.
+            let words = words
+                .into_iter()
+                .enumerate()
+                .map(|(i, word)| (word, i as u32))
+                .collect::<Vec<_>>();
+            let words = words
+                .iter()
+                .filter(|(word, i)| !words.iter().any(|(other, j)| other == word && j > i))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let matcher = Matcher::new(words.iter().map(|(word, i)| (word.as_str(), *i)));
+            let found = occurrences(&words, &line);
+
+            prop_assert_eq!(
+                matcher.first(&line),
+                found.iter().copied().min_by_key(|m| (m.start, usize::MAX - m.len))
+            );
+            prop_assert_eq!(
+                matcher.last(&line),
+                found.iter().copied().max_by_key(|m| (m.start + m.len, m.len))
+            );
+        }
+    }
+}
@@ -1,2 +1,5 @@
+pub mod matcher;
 pub mod part1;
 pub mod part2;
+pub mod scan;
+pub mod words;
@@ -1,3 +1,4 @@
+use super::scan::total;
 use crate::Challenge;
 
 /// --- Day 1: Trebuchet?! ---
@@ -31,7 +32,7 @@ use crate::Challenge;
 ///
 /// For example:
 ///
-/// ```
+/// ```text
 /// 1abc2
 /// pqr3stu8vwx
 /// a1b2c3d4e5f
@@ -62,31 +63,7 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
-
-        let mut sum = 0;
-        for line in input {
-            let mut first = None;
-            let mut last = None;
-
-            for char in line.chars() {
-                if char.is_ascii_digit() {
-                    let n = char.to_digit(10).unwrap();
-                    match first {
-                        None => first = Some(n),
-                        Some(_) => last = Some(n),
-                    }
-                }
-            }
-
-            if last.is_none() {
-                last = first;
-            }
-
-            sum += first.unwrap() * 10 + last.unwrap();
-        }
-
-        sum.to_string()
+        total(Self::input().as_bytes()).unwrap().to_string()
     }
 
     fn solution(&self) -> &'static str {
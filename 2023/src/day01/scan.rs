@@ -0,0 +1,49 @@
+use memchr::memchr;
+
+/// Sum of all lines' calibration values, each being a line's first and last digit.
+/// `None` if a line has no digits, or the sum doesn't fit.
+///
+/// Works on the whole document at once, without splitting it into lines first. Each
+/// line is read forwards up to its first digit. `memchr` then skips ahead to the end of
+/// the line, which is read backwards to its last digit. Bytes in between are never
+/// looked at one by one.
+pub fn total(document: &[u8]) -> Option<u32> {
+    let mut rest = document;
+    let mut sum = 0_u32;
+
+    while !rest.is_empty() {
+        let first = rest
+            .iter()
+            .position(|&b| b.is_ascii_digit() || b == b'\n')
+            .filter(|&first| rest[first] != b'\n')?;
+        let end = memchr(b'\n', &rest[first..]).map_or(rest.len(), |end| first + end);
+
+        // At worst, this finds the first digit again.
+        let last = rest[..end].iter().rposition(u8::is_ascii_digit)?;
+
+        sum = sum.checked_add(u32::from(rest[first] - b'0') * 10 + u32::from(rest[last] - b'0'))?;
+        rest = rest.get(end + 1..).unwrap_or_default();
+    }
+
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(include_str!("input/sample/1.txt"), Some(142))]
+    #[case("1abc2\n", Some(12))]
+    #[case("1abc2\r\npqr3stu8vwx\r\n", Some(50))]
+    #[case("7", Some(77))]
+    #[case("a0b", Some(0))]
+    #[case("", Some(0))]
+    #[case("12\nabc\n34", None)]
+    #[case("12\n\n34", None)]
+    #[case("abc", None)]
+    fn test_total(#[case] document: &str, #[case] expected: Option<u32>) {
+        assert_eq!(total(document.as_bytes()), expected);
+    }
+}
@@ -0,0 +1,357 @@
+use std::{fmt, num::ParseIntError};
+
+use smallvec::SmallVec;
+
+/// Cube counts by colour, for any set of colours. Colours not listed count as zero.
+#[derive(Debug, Clone, Default)]
+pub struct Cubes<'a> {
+    // A handful of colours at most: a list beats hashing. Kept inline for the usual
+    // three, as there's one per draw: allocating each took most of the parsing time.
+    counts: SmallVec<[(&'a str, u32); 3]>,
+}
+
+/// Cubes shown at once.
+pub type Draw<'a> = Cubes<'a>;
+
+/// Cubes in the bag.
+pub type Bag<'a> = Cubes<'a>;
+
+impl<'a> Cubes<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, colour: &str) -> u32 {
+        self.counts
+            .iter()
+            .find(|&&(c, _)| c == colour)
+            .map_or(0, |&(_, n)| n)
+    }
+
+    /// Adds `n` cubes of `colour`.
+    ///
+    /// # Panics
+    ///
+    /// If that's more than a `u32` holds; see [`try_add`](Self::try_add).
+    pub fn add(&mut self, colour: &'a str, n: u32) {
+        self.try_add(colour, n).expect("cube count overflows u32");
+    }
+
+    /// Adds `n` cubes of `colour`, unless that's more than a `u32` holds. Nothing
+    /// changes then.
+    pub fn try_add(&mut self, colour: &'a str, n: u32) -> Option<()> {
+        match self.counts.iter_mut().find(|(c, _)| *c == colour) {
+            Some((_, count)) => *count = count.checked_add(n)?,
+            None => self.counts.push((colour, n)),
+        }
+
+        Some(())
+    }
+
+    /// Colours with their counts, in order of first appearance.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, u32)> + '_ {
+        self.counts.iter().copied()
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.counts.iter().map(|&(colour, _)| colour)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().map(|&(_, n)| n).sum()
+    }
+
+    /// Whether these cubes could all have come out of `bag` at once.
+    pub fn fits_in(&self, bag: &Bag) -> bool {
+        self.iter().all(|(colour, n)| n <= bag.get(colour))
+    }
+
+    /// The most of each colour in either.
+    pub fn max(mut self, other: &Cubes<'a>) -> Cubes<'a> {
+        for (colour, n) in other.iter() {
+            match self.counts.iter_mut().find(|(c, _)| *c == colour) {
+                Some((_, count)) => *count = (*count).max(n),
+                None => self.counts.push((colour, n)),
+            }
+        }
+
+        self
+    }
+
+    /// Product of all counts. Only colours listed count, so there's no multiplying by
+    /// zero for colours never seen.
+    pub fn power(&self) -> u64 {
+        self.counts.iter().map(|&(_, n)| u64::from(n)).product()
+    }
+}
+
+/// Same counts for every colour, in whatever order.
+impl PartialEq for Cubes<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().all(|(colour, n)| other.get(colour) == n)
+            && other.iter().all(|(colour, n)| self.get(colour) == n)
+    }
+}
+
+impl Eq for Cubes<'_> {}
+
+impl<'a> FromIterator<(&'a str, u32)> for Cubes<'a> {
+    fn from_iter<I: IntoIterator<Item = (&'a str, u32)>>(iter: I) -> Self {
+        let mut cubes = Self::new();
+        for (colour, n) in iter {
+            cubes.add(colour, n);
+        }
+
+        cubes
+    }
+}
+
+/// One line of the record: a game, and everything drawn in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game<'a> {
+    pub id: u32,
+    pub draws: Vec<Draw<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGameError {
+    /// Not of the form `Game <id>: <draws>`.
+    InvalidGame(String),
+    /// Not of the form `<count> <colour>`.
+    InvalidCubes(String),
+    /// A colour repeated within a draw adds up to more than a `u32` holds.
+    TooManyCubes(String),
+    InvalidNumber(ParseIntError),
+}
+
+impl fmt::Display for ParseGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidGame(line) => write!(f, "invalid game {line:?}"),
+            Self::InvalidCubes(cubes) => write!(f, "invalid cubes {cubes:?}"),
+            Self::TooManyCubes(draw) => write!(f, "too many cubes in {draw:?}"),
+            Self::InvalidNumber(err) => write!(f, "invalid number: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseGameError {}
+
+impl From<ParseIntError> for ParseGameError {
+    fn from(err: ParseIntError) -> Self {
+        Self::InvalidNumber(err)
+    }
+}
+
+impl<'a> Game<'a> {
+    /// Parses `Game 1: 3 blue, 4 red; 1 red, 2 green`. Draws are separated by `;`, cubes
+    /// within a draw by `,`. Colours are whatever follows the count, spaces and all;
+    /// repeated ones within a draw add up.
+    pub fn parse(line: &'a str) -> Result<Self, ParseGameError> {
+        let (id, draws) = parse_draws(line)?;
+
+        Ok(Self {
+            id,
+            draws: draws.collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Whether every draw could have come out of `bag`.
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        self.draws.iter().all(|draw| draw.fits_in(bag))
+    }
+
+    /// The fewest cubes of each colour that make the game possible.
+    pub fn minimal_bag(&self) -> Bag<'a> {
+        self.draws
+            .iter()
+            .fold(Bag::new(), |bag, draw| bag.max(draw))
+    }
+
+    /// Power of the minimal bag.
+    pub fn power(&self) -> u64 {
+        self.minimal_bag().power()
+    }
+
+    /// All colours drawn, in order of first appearance.
+    pub fn colours(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let mut seen = Vec::new();
+
+        self.draws
+            .iter()
+            .flat_map(Cubes::colours)
+            .filter(move |colour| {
+                let new = !seen.contains(colour);
+                if new {
+                    seen.push(*colour);
+                }
+                new
+            })
+    }
+}
+
+/// Parses one game per line.
+pub fn parse_games(input: &str) -> Result<Vec<Game<'_>>, ParseGameError> {
+    input.lines().map(Game::parse).collect()
+}
+
+/// Splits a game line as described in [`Game::parse`] into its id and its draws. Draws
+/// are parsed as they're iterated, without allocating, so they can be folded right
+/// away, or not even all parsed.
+pub fn parse_draws(
+    line: &str,
+) -> Result<(u32, impl Iterator<Item = Result<Draw<'_>, ParseGameError>>), ParseGameError> {
+    let (id, record) = line
+        .strip_prefix("Game ")
+        .and_then(|line| line.split_once(':'))
+        .ok_or_else(|| ParseGameError::InvalidGame(line.to_owned()))?;
+
+    let draws = record.split(';').map(|draw| {
+        let mut cubes = Draw::new();
+
+        for amount_and_colour in draw.split(',') {
+            let (n, colour) = amount_and_colour
+                .trim_start()
+                .split_once(' ')
+                .filter(|(_, colour)| !colour.is_empty())
+                .ok_or_else(|| ParseGameError::InvalidCubes(amount_and_colour.to_owned()))?;
+
+            cubes
+                .try_add(colour, n.parse()?)
+                .ok_or_else(|| ParseGameError::TooManyCubes(draw.to_owned()))?;
+        }
+
+        Ok(cubes)
+    });
+
+    Ok((id.parse()?, draws))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    fn bag(cubes: &[(&'static str, u32)]) -> Bag<'static> {
+        cubes.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let game = Game::parse("Game 12: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+
+        assert_eq!(game.id, 12);
+        assert_eq!(
+            game.draws,
+            [
+                bag(&[("blue", 3), ("red", 4)]),
+                bag(&[("red", 1), ("green", 2), ("blue", 6)]),
+                bag(&[("green", 2)]),
+            ]
+        );
+        assert_eq!(game.colours().collect::<Vec<_>>(), ["blue", "red", "green"]);
+    }
+
+    #[rstest]
+    #[case("Gaem 1: 1 red", ParseGameError::InvalidGame("Gaem 1: 1 red".to_owned()))]
+    #[case("Game 1 1 red", ParseGameError::InvalidGame("Game 1 1 red".to_owned()))]
+    #[case("Game 1: 1red", ParseGameError::InvalidCubes(" 1red".to_owned()))]
+    #[case("Game 1: 1 red; ", ParseGameError::InvalidCubes(" ".to_owned()))]
+    #[case("Game 1: 1 ", ParseGameError::InvalidCubes(" 1 ".to_owned()))]
+    #[case(
+        "Game 1: 4294967295 red, 1 red",
+        ParseGameError::TooManyCubes(" 4294967295 red, 1 red".to_owned())
+    )]
+    fn test_parse_errors(#[case] line: &str, #[case] expected: ParseGameError) {
+        assert_eq!(Game::parse(line), Err(expected));
+    }
+
+    #[test]
+    fn test_parse_draws() {
+        let (id, mut draws) = parse_draws("Game 3: 1 red, 2 blue; 1 x y; x").unwrap();
+
+        assert_eq!(id, 3);
+        assert_eq!(draws.next(), Some(Ok(bag(&[("red", 1), ("blue", 2)]))));
+        assert_eq!(draws.next(), Some(Ok(bag(&[("x y", 1)]))));
+        // Only parsed once reached.
+        assert_eq!(
+            draws.next(),
+            Some(Err(ParseGameError::InvalidCubes(" x".to_owned())))
+        );
+        assert_eq!(draws.next(), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_numbers() {
+        assert!(matches!(
+            Game::parse("Game one: 1 red"),
+            Err(ParseGameError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            Game::parse("Game 1: -1 red"),
+            Err(ParseGameError::InvalidNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_sample() {
+        let games = parse_games(SAMPLE).unwrap();
+        let limit = bag(&[("red", 12), ("green", 13), ("blue", 14)]);
+
+        assert_eq!(
+            games
+                .iter()
+                .filter(|game| game.is_possible_with(&limit))
+                .map(|game| game.id)
+                .collect::<Vec<_>>(),
+            [1, 2, 5]
+        );
+        assert_eq!(
+            games.iter().map(Game::power).collect::<Vec<_>>(),
+            [48, 12, 1560, 630, 36]
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_colours() {
+        let game = Game::parse("Game 7: 2 cyan, 1 magenta; 3 yellow, 1 cyan, 2 cyan").unwrap();
+        let dark = Game::parse("Game 8: 1 dark red, 2 red").unwrap();
+
+        assert_eq!(
+            game.minimal_bag(),
+            bag(&[("cyan", 3), ("magenta", 1), ("yellow", 3)])
+        );
+        assert_eq!(game.power(), 9);
+        assert!(game.is_possible_with(&bag(&[("cyan", 3), ("magenta", 1), ("yellow", 3)])));
+        assert!(!game.is_possible_with(&bag(&[("cyan", 2), ("magenta", 9), ("yellow", 9)])));
+        assert_eq!(dark.minimal_bag(), bag(&[("dark red", 1), ("red", 2)]));
+        // Colours missing from the bag don't fit at all.
+        assert!(!game.is_possible_with(&bag(&[("cyan", 9), ("yellow", 9)])));
+    }
+
+    #[test]
+    fn test_cubes() {
+        let a = bag(&[("red", 2), ("blue", 5)]);
+        let b = bag(&[("blue", 3), ("green", 1)]);
+
+        assert_eq!(a.get("red"), 2);
+        assert_eq!(a.get("green"), 0);
+        assert_eq!(
+            a.clone().max(&b),
+            bag(&[("red", 2), ("blue", 5), ("green", 1)])
+        );
+        assert_eq!(a.total(), 7);
+        assert_eq!(a, bag(&[("blue", 5), ("red", 2), ("green", 0)]));
+        assert_ne!(a, bag(&[("red", 2)]));
+        assert!(bag(&[("red", 2)]).fits_in(&a));
+        assert!(!b.fits_in(&a));
+        assert!(Cubes::new().fits_in(&Cubes::new()));
+        assert_eq!(Cubes::new().power(), 1);
+
+        let mut c = a.clone();
+        assert_eq!(c.try_add("red", u32::MAX), None);
+        assert_eq!(c, a);
+    }
+}
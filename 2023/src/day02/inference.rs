@@ -0,0 +1,452 @@
+use std::{fmt, ops::RangeInclusive};
+
+use super::game::{Bag, Game};
+
+/// What a set of games says about the bag they were all played with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Inference<'a> {
+    /// How sure `interval`s are, e.g. `0.95`.
+    pub confidence: f64,
+    pub estimates: Vec<Estimate<'a>>,
+}
+
+/// What's known about one colour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Estimate<'a> {
+    pub colour: &'a str,
+    /// The most ever drawn at once: there are at least this many.
+    pub minimal: u32,
+    /// Maximum likelihood estimate.
+    pub likely: u32,
+    /// Likelihood ratio interval around `likely`.
+    pub interval: RangeInclusive<u32>,
+}
+
+/// The fewest cubes of each colour that make all games possible: the most of each ever
+/// drawn in any of them.
+pub fn minimal_bag<'a>(games: &[Game<'a>]) -> Bag<'a> {
+    games
+        .iter()
+        .fold(Bag::new(), |bag, game| bag.max(&game.minimal_bag()))
+}
+
+/// Estimates the bag all `games` were played with, assuming every draw is a uniformly
+/// random subset of it. Colours are those drawn in any game, in order of appearance.
+///
+/// Picking uniformly among all subsets means each cube is in a draw with probability ½,
+/// independently of all others. How many of a colour are drawn is then binomial, with
+/// the bag's count `n` and ½ as parameters, and each colour can be estimated on its own:
+/// `n` maximises the likelihood `∏ C(n, k) / 2ⁿ` over all draws' counts `k`. Intervals
+/// hold every `n` whose likelihood is close enough to the maximum for a χ² test with one
+/// degree of freedom at `confidence` (Wilks' theorem).
+///
+/// Draws averaging less than half the minimal bag don't fit that model: the likelihood
+/// then only drops from the minimal bag on, and that's the estimate. Puzzle inputs are
+/// like that. Estimates and intervals past `u32::MAX` are cut off there.
+///
+/// Panics unless `0 < confidence < 1`.
+pub fn infer<'a>(games: &[Game<'a>], confidence: f64) -> Inference<'a> {
+    assert!(
+        0.0 < confidence && confidence < 1.0,
+        "confidence must be between 0 and 1, not {confidence}"
+    );
+
+    let bag = minimal_bag(games);
+    let z = normal_quantile((1.0 + confidence) / 2.0);
+    let threshold = z * z / 2.0;
+
+    let estimates = bag
+        .iter()
+        .map(|(colour, minimal)| {
+            // Draws not showing a colour show zero of it.
+            let counts = games
+                .iter()
+                .flat_map(|game| &game.draws)
+                .map(|draw| draw.get(colour))
+                .collect::<Vec<_>>();
+
+            let likely = first_in(minimal..=u32::MAX, |n| {
+                log_likelihood_step(&counts, n) <= 0.0
+            })
+            .unwrap_or(u32::MAX);
+
+            // Log likelihood drops from the maximum both ways, the interval is where it
+            // hasn't dropped past the threshold yet.
+            let maximum = log_likelihood(&counts, likely);
+            let drop = |n| maximum - log_likelihood(&counts, n);
+
+            let low = first_in(minimal..=likely, |n| drop(n) <= threshold).unwrap_or(likely);
+            let high =
+                first_in(likely..=u32::MAX, |n| drop(n) > threshold).map_or(u32::MAX, |n| n - 1);
+
+            Estimate {
+                colour,
+                minimal,
+                likely,
+                interval: low..=high,
+            }
+        })
+        .collect();
+
+    Inference {
+        confidence,
+        estimates,
+    }
+}
+
+/// How much the log likelihood of a bag of `n` grows by adding one more cube: `n + 1`
+/// over `n` cubes. `n` must be at least every count.
+///
+/// Going from `C(n, k) / 2ⁿ` to `C(n + 1, k) / 2ⁿ⁺¹` multiplies by ½, and by
+/// `(n + 1) / (n + 1 - k)`. Each step is smaller than the one before (the likelihood is
+/// log-concave), so the maximum is right before the first one that's not positive.
+fn log_likelihood_step(counts: &[u32], n: u32) -> f64 {
+    let n = f64::from(n) + 1.0;
+
+    counts
+        .iter()
+        .map(|&k| (n / (n - f64::from(k))).ln() - std::f64::consts::LN_2)
+        .sum()
+}
+
+/// Log likelihood of a bag of `n`, up to a constant, which cancels out comparing bags.
+/// `n` must be at least every count.
+fn log_likelihood(counts: &[u32], n: u32) -> f64 {
+    let n = f64::from(n);
+
+    counts
+        .iter()
+        .map(|&k| ln_gamma(n + 1.0) - ln_gamma(n - f64::from(k) + 1.0) - n * std::f64::consts::LN_2)
+        .sum()
+}
+
+/// The first `n` in `range` that `holds`, given that it also holds for all `n` after
+/// that one. Gallops up to a bound, then bisects, so it takes logarithmic time, however
+/// far into `range` that `n` is.
+fn first_in(range: RangeInclusive<u32>, holds: impl Fn(u32) -> bool) -> Option<u32> {
+    let (mut low, end) = range.into_inner();
+    let mut high = low;
+    let mut step = 1_u32;
+
+    while !holds(high) {
+        if high >= end {
+            return None;
+        }
+
+        low = high + 1;
+        high = high.saturating_add(step).min(end);
+        step = step.saturating_mul(2);
+    }
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        if holds(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    Some(low)
+}
+
+/// `ln Γ(x)` for `x ≥ 1`, after Lanczos' approximation (`g = 7`, nine coefficients).
+/// Relative error is around 10⁻¹⁵: even for billions of cubes, log likelihoods are
+/// then off by far less than intervals care about.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const P: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let series = P[1..]
+        .iter()
+        .zip(1..)
+        .fold(P[0], |sum, (&p, i)| sum + p / (x + f64::from(i)));
+
+    0.5 * std::f64::consts::TAU.ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// The standard normal distribution's quantile function, after Peter Acklam's rational
+/// approximation. Relative error is below 1.2 × 10⁻⁹, plenty for picking intervals.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const LOW: f64 = 0.02425;
+
+    // Horner's method, with an implied leading 1 for denominators.
+    let poly = |coefficients: &[f64], x: f64| coefficients.iter().fold(0.0, |acc, &c| acc * x + c);
+    let tail = |q: f64| poly(&C, q) / (poly(&D, q) * q + 1.0);
+
+    if p < LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        poly(&A, r) * q / (poly(&B, r) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+impl Inference<'_> {
+    /// The most likely bag.
+    pub fn likely_bag(&self) -> Bag<'_> {
+        self.estimates
+            .iter()
+            .map(|estimate| (estimate.colour, estimate.likely))
+            .collect()
+    }
+}
+
+impl fmt::Display for Inference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .estimates
+            .iter()
+            .map(|estimate| estimate.colour.len())
+            .chain(["colour".len()])
+            .max()
+            .unwrap();
+        let confidence = format!("{}% interval", self.confidence * 100.0);
+
+        write!(f, "{:width$}  minimal  likely  {confidence}", "colour")?;
+        for estimate in &self.estimates {
+            write!(
+                f,
+                "\n{:width$}  {:>7}  {:>6}  {}..={}",
+                estimate.colour,
+                estimate.minimal,
+                estimate.likely,
+                estimate.interval.start(),
+                estimate.interval.end(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day02::game::{parse_games, Cubes};
+    use rstest::rstest;
+
+    /// Games of draws, each cube of `bag` in a draw by a (pseudo) coin flip.
+    fn simulate(bag: &[(&'static str, u32)], games: usize, draws: usize) -> Vec<Game<'static>> {
+        // xorshift64: plenty random for this, and reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut flips = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        (1..=games as u32)
+            .map(|id| Game {
+                id,
+                draws: (0..draws)
+                    .map(|_| {
+                        bag.iter()
+                            .map(|&(colour, n)| (colour, (flips() & ((1 << n) - 1)).count_ones()))
+                            .filter(|&(_, n)| n > 0)
+                            .collect::<Cubes>()
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_minimal_bag() {
+        let games = parse_games(include_str!("input/sample.txt")).unwrap();
+
+        assert_eq!(
+            minimal_bag(&games),
+            [("red", 20), ("green", 13), ("blue", 15)]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[rstest]
+    // `n / 2ⁿ` is ½ for both 1 and 2: the smaller one wins.
+    #[case(&[1], 1)]
+    // `C(n, 2) / 2ⁿ` is ⅜ for both 3 and 4.
+    #[case(&[2], 3)]
+    #[case(&[0, 0], 0)]
+    #[case(&[3, 5, 4], 8)]
+    fn test_likely(#[case] counts: &[u32], #[case] expected: u32) {
+        let games = counts
+            .iter()
+            .map(|&n| Game {
+                id: 1,
+                draws: vec![[("red", n)].into_iter().collect()],
+            })
+            .collect::<Vec<_>>();
+
+        let inference = infer(&games, 0.95);
+
+        assert_eq!(inference.estimates[0].likely, expected);
+    }
+
+    #[test]
+    fn test_simulated() {
+        let bag = [("red", 12), ("green", 13), ("blue", 14), ("cyan", 40)];
+        let games = simulate(&bag, 100, 20);
+        let inference = infer(&games, 0.95);
+
+        assert_eq!(inference.likely_bag(), bag.into_iter().collect());
+        for (estimate, (colour, n)) in inference.estimates.iter().zip(bag) {
+            assert_eq!(estimate.colour, colour);
+            assert!(estimate.minimal <= n);
+            assert!(estimate.interval.contains(&n));
+            assert!(estimate.interval.contains(&estimate.likely));
+        }
+    }
+
+    #[test]
+    fn test_intervals_narrow() {
+        let bag = [("red", 30)];
+        let few = infer(&simulate(&bag, 5, 2), 0.95).estimates[0].clone();
+        let many = infer(&simulate(&bag, 500, 2), 0.95).estimates[0].clone();
+        let sure = infer(&simulate(&bag, 5, 2), 0.999).estimates[0].clone();
+
+        let width = |estimate: &Estimate| estimate.interval.end() - estimate.interval.start();
+        assert!(width(&many) < width(&few));
+        assert!(width(&sure) > width(&few));
+    }
+
+    #[test]
+    fn test_interval_edges() {
+        // Summing steps, like walking away from the estimate one cube at a time.
+        let games = simulate(&[("red", 30)], 20, 3);
+        let estimate = &infer(&games, 0.95).estimates[0];
+        let counts = games
+            .iter()
+            .flat_map(|game| &game.draws)
+            .map(|draw| draw.get("red"))
+            .collect::<Vec<_>>();
+        let threshold = normal_quantile(0.975).powi(2) / 2.0;
+
+        let steps = |range: std::ops::Range<u32>| {
+            range.map(|n| log_likelihood_step(&counts, n)).sum::<f64>()
+        };
+        let drop = |n: u32| {
+            if n < estimate.likely {
+                steps(n..estimate.likely)
+            } else {
+                -steps(estimate.likely..n)
+            }
+        };
+
+        let (low, high) = (*estimate.interval.start(), *estimate.interval.end());
+        assert!(low > estimate.minimal);
+        assert!(drop(low - 1) > threshold && drop(low) <= threshold);
+        assert!(drop(high) <= threshold && drop(high + 1) > threshold);
+    }
+
+    #[test]
+    fn test_large_counts() {
+        // Would take billions of steps to walk, and the estimate doesn't fit a `u32`.
+        let games = (1..=2)
+            .map(|id| Game {
+                id,
+                draws: vec![[("red", 3_000_000_000)].into_iter().collect()],
+            })
+            .collect::<Vec<_>>();
+        let estimate = &infer(&games, 0.95).estimates[0];
+
+        assert_eq!(estimate.minimal, 3_000_000_000);
+        assert_eq!(estimate.likely, u32::MAX);
+        assert_eq!(estimate.interval.end(), &u32::MAX);
+        assert!(estimate.interval.start() > &3_000_000_000);
+    }
+
+    #[rstest]
+    #[case(1.0, 0.0)]
+    #[case(2.0, 0.0)]
+    #[case(11.0, 15.104_412_573_075_514)]
+    #[case(1e9 + 1.0, 19_723_265_848.226_982)]
+    fn test_ln_gamma(#[case] x: f64, #[case] expected: f64) {
+        assert!((ln_gamma(x) - expected).abs() <= 1e-12 * expected.max(1.0));
+    }
+
+    #[rstest]
+    #[case(0.5, 0.0)]
+    #[case(0.975, 1.959_963_985)]
+    #[case(0.995, 2.575_829_304)]
+    #[case(0.01, -2.326_347_874)]
+    fn test_normal_quantile(#[case] p: f64, #[case] expected: f64) {
+        assert!((normal_quantile(p) - expected).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_display() {
+        let inference = Inference {
+            confidence: 0.95,
+            estimates: vec![
+                Estimate {
+                    colour: "red",
+                    minimal: 12,
+                    likely: 13,
+                    interval: 12..=15,
+                },
+                Estimate {
+                    colour: "turquoise",
+                    minimal: 1,
+                    likely: 2,
+                    interval: 1..=4,
+                },
+            ],
+        };
+
+        assert_eq!(
+            inference.to_string(),
+            "\
+colour     minimal  likely  95% interval
+red             12      13  12..=15
+turquoise        1       2  1..=4"
+        );
+    }
+}
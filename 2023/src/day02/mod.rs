@@ -1,2 +1,4 @@
+pub mod game;
+pub mod inference;
 pub mod part1;
 pub mod part2;
@@ -1,3 +1,4 @@
+use super::game::{parse_draws, Bag};
 use crate::Challenge;
 
 /// --- Day 2: Cube Conundrum ---
@@ -88,28 +89,18 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
+        let bag = [("red", 12), ("green", 13), ("blue", 14)]
+            .into_iter()
+            .collect::<Bag>();
 
-        let mut sum = 0;
-        'outer: for line in input {
-            let (_, line) = line.split_once(' ').unwrap();
-            let (game_id, line) = line.split_once(':').unwrap();
-            let game_id: u32 = game_id.parse().unwrap();
-
-            for draw in line.split(';') {
-                for amount_and_color in draw.split(',') {
-                    let (amount, color) = amount_and_color.trim_start().split_once(' ').unwrap();
-                    let amount: u32 = amount.parse().unwrap();
-
-                    if let ("red", 13..) | ("green", 14..) | ("blue", 15..) = (color, amount) {
-                        continue 'outer;
-                    }
-                }
-            }
-
-            sum += game_id;
-        }
-
-        sum.to_string()
+        // Draws after the first one that doesn't fit don't matter, so aren't parsed.
+        Self::input()
+            .lines()
+            .map(|line| parse_draws(line).unwrap())
+            .filter_map(|(id, mut draws)| {
+                draws.all(|draw| draw.unwrap().fits_in(&bag)).then_some(id)
+            })
+            .sum::<u32>()
+            .to_string()
     }
 }
@@ -1,3 +1,4 @@
+use super::game::{parse_draws, Bag};
 use crate::Challenge;
 
 /// --- Part Two ---
@@ -37,12 +38,6 @@ use crate::Challenge;
 /// the sum of the power of these sets?
 pub struct Part {}
 
-struct Color {
-    red: u32,
-    green: u32,
-    blue: u32,
-}
-
 impl Challenge for Part {
     fn input() -> &'static str {
         #[cfg(debug_assertions)]
@@ -77,36 +72,15 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
-
-        let mut sum = 0;
-        for line in input {
-            let (_, line) = line.split_once(' ').unwrap();
-            let (_, line) = line.split_once(':').unwrap();
-
-            let mut maximums = Color {
-                red: 0,
-                green: 0,
-                blue: 0,
-            };
-
-            for draw in line.split(';') {
-                for amount_and_color in draw.split(',') {
-                    let (amount, color) = amount_and_color.trim_start().split_once(' ').unwrap();
-                    let amount: u32 = amount.parse().unwrap();
-
-                    match color {
-                        "red" => maximums.red = maximums.red.max(amount),
-                        "green" => maximums.green = maximums.green.max(amount),
-                        "blue" => maximums.blue = maximums.blue.max(amount),
-                        _ => unreachable!(),
-                    }
-                }
-            }
-
-            sum += maximums.red * maximums.green * maximums.blue;
-        }
+        Self::input()
+            .lines()
+            .map(|line| {
+                let (_, draws) = parse_draws(line).unwrap();
+                let bag = draws.fold(Bag::new(), |bag, draw| bag.max(&draw.unwrap()));
 
-        sum.to_string()
+                bag.power()
+            })
+            .sum::<u64>()
+            .to_string()
     }
 }
@@ -0,0 +1,154 @@
+use std::ops::RangeInclusive;
+
+use smallvec::SmallVec;
+
+use super::schematic::{Number, Schematic, Symbol};
+
+/// How a gear's numbers combine into its ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    /// Zero for a gear without numbers.
+    Max,
+}
+
+impl Aggregate {
+    /// `None` on overflow.
+    pub fn apply<'a>(self, numbers: impl IntoIterator<Item = &'a Number>) -> Option<u64> {
+        let mut values = numbers.into_iter().map(|number| u64::from(number.value));
+
+        match self {
+            Self::Product => values.try_fold(1_u64, u64::checked_mul),
+            Self::Sum => values.try_fold(0_u64, u64::checked_add),
+            Self::Max => Some(values.max().unwrap_or_default()),
+        }
+    }
+}
+
+/// What makes a symbol a gear, and what its ratio is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule<'a> {
+    /// Symbols that can be gears.
+    pub symbols: &'a str,
+    /// How many numbers a gear touches.
+    pub neighbours: RangeInclusive<usize>,
+    pub aggregate: Aggregate,
+}
+
+/// The puzzle's: a `*` touching exactly two numbers, multiplied together.
+impl Default for GearRule<'_> {
+    fn default() -> Self {
+        Self {
+            symbols: "*",
+            neighbours: 2..=2,
+            aggregate: Aggregate::Product,
+        }
+    }
+}
+
+/// A symbol the rule made a gear, with the numbers it touches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gear<'a> {
+    pub symbol: &'a Symbol,
+    // A symbol touches six numbers at most: two above, two below, one on either side.
+    pub numbers: SmallVec<[&'a Number; 6]>,
+}
+
+impl GearRule<'_> {
+    /// All gears in `schematic`, top to bottom, left to right.
+    pub fn gears<'s>(&'s self, schematic: &'s Schematic) -> impl Iterator<Item = Gear<'s>> + 's {
+        schematic
+            .symbols()
+            .iter()
+            .filter(|symbol| self.symbols.contains(symbol.char))
+            .map(|symbol| Gear {
+                symbol,
+                numbers: schematic.numbers_touching(symbol).collect(),
+            })
+            .filter(|gear| self.neighbours.contains(&gear.numbers.len()))
+    }
+
+    /// Ratio of `gear`, `None` on overflow.
+    pub fn ratio(&self, gear: &Gear) -> Option<u64> {
+        self.aggregate.apply(gear.numbers.iter().copied())
+    }
+
+    /// Sum of all gears' ratios, `None` on overflow.
+    pub fn total(&self, schematic: &Schematic) -> Option<u64> {
+        self.gears(schematic)
+            .try_fold(0_u64, |sum, gear| sum.checked_add(self.ratio(&gear)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    #[rstest]
+    #[case(GearRule::default(), 467835)]
+    // `617*` joins in, with just one number.
+    #[case(GearRule { neighbours: 1..=2, ..Default::default() }, 467835 + 617)]
+    #[case(GearRule { aggregate: Aggregate::Sum, ..Default::default() }, 467 + 35 + 755 + 598)]
+    #[case(GearRule { aggregate: Aggregate::Max, ..Default::default() }, 467 + 755)]
+    #[case(
+        GearRule { symbols: "#$", neighbours: 0..=6, aggregate: Aggregate::Sum },
+        633 + 664
+    )]
+    #[case(GearRule { symbols: "", ..Default::default() }, 0)]
+    fn test_total(#[case] rule: GearRule, #[case] expected: u64) {
+        let schematic: Schematic = SAMPLE.parse().unwrap();
+
+        assert_eq!(rule.total(&schematic), Some(expected));
+    }
+
+    #[test]
+    fn test_gears() {
+        let schematic: Schematic = SAMPLE.parse().unwrap();
+        let rule = GearRule {
+            neighbours: 1..=1,
+            ..Default::default()
+        };
+        let gears = rule.gears(&schematic).collect::<Vec<_>>();
+
+        assert_eq!(gears.len(), 1);
+        assert_eq!(gears[0].symbol.position, (4, 3));
+        assert_eq!(gears[0].numbers[0].value, 617);
+        assert_eq!(rule.ratio(&gears[0]), Some(617));
+    }
+
+    #[test]
+    fn test_crowded() {
+        // As many numbers as fit around one symbol.
+        let schematic: Schematic = "1.2\n3*4\n5.6".parse().unwrap();
+        let rule = GearRule {
+            neighbours: 6..=6,
+            ..Default::default()
+        };
+
+        assert_eq!(rule.total(&schematic), Some(720));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let schematic: Schematic = "\
+4000000000.4000000000
+..........*..........
+4000000000.4000000000"
+            .parse()
+            .unwrap();
+        let rule = GearRule {
+            neighbours: 4..=4,
+            ..Default::default()
+        };
+
+        assert_eq!(rule.total(&schematic), None);
+        assert_eq!(
+            Aggregate::Sum.apply(schematic.numbers()),
+            Some(16_000_000_000)
+        );
+    }
+}
@@ -1,5 +1,4 @@
-use crate::iter::CenteredWindowExt;
-use crate::Challenge;
+use crate::{day03::schematic::Schematic, Challenge};
 
 /// --- Day 3: Gear Ratios ---
 ///
@@ -48,87 +47,6 @@ use crate::Challenge;
 /// part numbers in the engine schematic?
 pub struct Part {}
 
-pub const BASE: u32 = 10;
-
-#[derive(Debug, Clone, Copy)]
-enum Number {
-    Valid(u32),
-    Invalid(u32),
-}
-
-impl Default for Number {
-    fn default() -> Self {
-        Self::Invalid(0)
-    }
-}
-
-impl Number {
-    fn value(&self) -> u32 {
-        match self {
-            Self::Valid(value) => *value,
-            Self::Invalid(value) => *value,
-        }
-    }
-
-    fn enlarge(&mut self, other: u32) -> &mut Self {
-        match self {
-            Self::Valid(value) => {
-                *value *= BASE;
-                *value += other;
-            }
-            Self::Invalid(value) => {
-                *value *= BASE;
-                *value += other;
-            }
-        }
-
-        self
-    }
-
-    fn transition_from_neighbors(
-        &mut self,
-        up: Option<&str>,
-        down: Option<&str>,
-        left: Option<char>,
-        right: Option<char>,
-    ) -> &mut Self {
-        match self {
-            Self::Valid(_) => self,
-            Self::Invalid(_) => {
-                let is_symbol = |char: char| !char.is_ascii_digit() && char != '.';
-
-                for snippet in &[up, down] {
-                    match snippet {
-                        Some(line) => {
-                            for char in line.chars() {
-                                if is_symbol(char) {
-                                    *self = Self::Valid(self.value());
-                                    return self;
-                                }
-                            }
-                        }
-                        None => {}
-                    }
-                }
-
-                for char in &[left, right] {
-                    match char {
-                        Some(char) => {
-                            if is_symbol(*char) {
-                                *self = Self::Valid(self.value());
-                                return self;
-                            }
-                        }
-                        None => {}
-                    }
-                }
-
-                self
-            }
-        }
-    }
-}
-
 impl Challenge for Part {
     fn input() -> &'static str {
         #[cfg(debug_assertions)]
@@ -163,54 +81,9 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
-
-        let mut sum = 0;
+        let schematic: Schematic = Self::input().parse().unwrap();
 
-        // This is the state: are we currently processing a number?
-        let mut number = None;
-
-        for (prev_line, current_line, next_line) in input.centered_window() {
-            let length = current_line.len();
-
-            for (i_char, (prev_char, curr_char, next_char)) in
-                current_line.chars().centered_window().enumerate()
-            {
-                let start = i_char.saturating_sub(1);
-                let end = (i_char + 1).min(length - 1);
-                let range = start..=end;
-
-                let up = || prev_line.map(|line| &line[range.clone()]);
-                let down = || next_line.map(|line| &line[range.clone()]);
-
-                match (number, curr_char.to_digit(BASE)) {
-                    (None, None) => continue,
-                    (None, Some(digit)) => {
-                        let mut n = Number::default();
-                        n.enlarge(digit);
-                        n.transition_from_neighbors(up(), down(), prev_char, next_char);
-                        number = Some(n);
-                    }
-                    (Some(mut n), Some(digit)) => {
-                        n.enlarge(digit);
-                        n.transition_from_neighbors(up(), down(), prev_char, next_char);
-
-                        // Not sure why this is necessary; aren't we mutating in place?
-                        number = Some(n);
-                    }
-                    (Some(n), None) => {
-                        match n {
-                            Number::Valid(value) => {
-                                sum += value;
-                            }
-                            Number::Invalid(_) => {}
-                        };
-
-                        number = None;
-                    }
-                }
-            }
-        }
+        let sum: u32 = schematic.part_numbers().map(|number| number.value).sum();
 
         format!("{}", sum)
     }
@@ -1,6 +1,7 @@
-use crate::{day03::part1::BASE, iter::CenteredWindowExt, Challenge};
-use rangetools::Rangetools;
-use regex::Regex;
+use crate::{
+    day03::{gear::GearRule, schematic::Schematic},
+    Challenge,
+};
 
 /// --- Part Two ---
 ///
@@ -80,81 +81,8 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
+        let schematic: Schematic = Self::input().parse().unwrap();
 
-        // Finally giving up and using a regex... wanted to try stdlib-only. This
-        // compilation takes about 100 µs, doubling the runtime of this solution.
-        let pattern = Regex::new(r"\d+").unwrap();
-
-        let mut sum: u64 = 0;
-
-        for (_i_line, (prev_line, curr_line, next_line)) in input.centered_window().enumerate() {
-            let length = curr_line.len();
-
-            'chars: for (i_char, (prev_char, curr_char, next_char)) in
-                curr_line.chars().centered_window().enumerate()
-            {
-                if curr_char != '*' {
-                    continue;
-                }
-
-                let start = i_char.saturating_sub(1);
-                let end = (i_char + 1).min(length - 1);
-                let range = start..=end;
-
-                let mut gear_ratio = 1;
-                let mut n = 0;
-                const MAX_NEIGHBORS: u32 = 2;
-
-                if let Some(char) = prev_char {
-                    let mut number = 0;
-                    if char.is_ascii_digit() {
-                        for (i, c) in curr_line[..i_char]
-                            .chars()
-                            .rev()
-                            .take_while(|c| c.is_ascii_digit())
-                            .enumerate()
-                        {
-                            number += c.to_digit(BASE).unwrap() * BASE.pow(i as u32);
-                        }
-
-                        n += 1;
-                        gear_ratio *= number;
-                    }
-                }
-
-                if let Some(char) = next_char {
-                    if char.is_ascii_digit() {
-                        let number: String = curr_line[i_char + 1..]
-                            .chars()
-                            .take_while(|c| c.is_ascii_digit())
-                            .collect();
-
-                        n += 1;
-                        gear_ratio *= number.parse::<u32>().unwrap();
-                    }
-                }
-
-                for line in [prev_line, next_line].iter().flatten() {
-                    for match_ in pattern.find_iter(line) {
-                        if range.clone().intersects(match_.range()) {
-                            n += 1;
-
-                            if n > MAX_NEIGHBORS {
-                                continue 'chars;
-                            }
-
-                            gear_ratio *= match_.as_str().parse::<u32>().unwrap();
-                        }
-                    }
-                }
-
-                if n == MAX_NEIGHBORS {
-                    sum += gear_ratio as u64;
-                }
-            }
-        }
-
-        format!("{}", sum)
+        format!("{}", GearRule::default().total(&schematic).unwrap())
     }
 }
@@ -0,0 +1,297 @@
+use std::{fmt, ops::Range, str::FromStr};
+
+use crate::grid::{Grid, ParseGridError, Position};
+
+/// A run of digits along a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+/// Anything that's neither a digit nor `.`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub char: char,
+    pub position: Position,
+}
+
+/// An engine schematic: all numbers and symbols, indexed by row to look up what's
+/// adjacent to what without scanning anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// Each row's numbers, as a range of `numbers`; same for symbols. Within a row,
+    /// they're sorted by column.
+    number_rows: Vec<Range<usize>>,
+    symbol_rows: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSchematicError {
+    InvalidGrid(ParseGridError),
+    /// Number starting here doesn't fit a `u32`.
+    NumberTooLarge(Position),
+}
+
+impl fmt::Display for ParseSchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidGrid(err) => write!(f, "invalid grid: {err}"),
+            Self::NumberTooLarge(position) => write!(f, "number at {position:?} is too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseSchematicError {}
+
+impl From<ParseGridError> for ParseSchematicError {
+    fn from(err: ParseGridError) -> Self {
+        Self::InvalidGrid(err)
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = ParseSchematicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid: Grid<char> = s.parse()?;
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut number_rows = Vec::with_capacity(grid.height());
+        let mut symbol_rows = Vec::with_capacity(grid.height());
+
+        for (i, row) in grid.rows().enumerate() {
+            let (first_number, first_symbol) = (numbers.len(), symbols.len());
+            // Whether the last cell was a digit, i.e. the last number is still being read.
+            let mut reading = false;
+
+            for (j, &char) in row.iter().enumerate() {
+                match char.to_digit(10) {
+                    Some(digit) if reading => {
+                        let number: &mut Number = numbers.last_mut().unwrap();
+                        number.value = number
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(digit))
+                            .ok_or(ParseSchematicError::NumberTooLarge((
+                                i,
+                                number.columns.start,
+                            )))?;
+                        number.columns.end = j + 1;
+                    }
+                    Some(digit) => {
+                        reading = true;
+                        numbers.push(Number {
+                            value: digit,
+                            row: i,
+                            columns: j..j + 1,
+                        });
+                    }
+                    None => {
+                        reading = false;
+
+                        if char != '.' {
+                            symbols.push(Symbol {
+                                char,
+                                position: (i, j),
+                            });
+                        }
+                    }
+                }
+            }
+
+            number_rows.push(first_number..numbers.len());
+            symbol_rows.push(first_symbol..symbols.len());
+        }
+
+        Ok(Self {
+            numbers,
+            symbols,
+            number_rows,
+            symbol_rows,
+        })
+    }
+}
+
+impl Schematic {
+    /// All numbers, top to bottom, left to right.
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// All symbols, top to bottom, left to right.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Numbers adjacent to `symbol`, diagonally included.
+    pub fn numbers_touching(&self, symbol: &Symbol) -> impl Iterator<Item = &Number> + '_ {
+        let (i, j) = symbol.position;
+
+        around(&self.numbers, &self.number_rows, i, j..j + 1, |number| {
+            number.columns.clone()
+        })
+    }
+
+    /// Symbols adjacent to `number`, diagonally included.
+    pub fn symbols_touching(&self, number: &Number) -> impl Iterator<Item = &Symbol> + '_ {
+        around(
+            &self.symbols,
+            &self.symbol_rows,
+            number.row,
+            number.columns.clone(),
+            |symbol| symbol.position.1..symbol.position.1 + 1,
+        )
+    }
+
+    /// Numbers adjacent to any symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .filter(|number| self.symbols_touching(number).next().is_some())
+    }
+}
+
+/// Items in `row` and the rows either side of it, spanning any of `columns` or the
+/// columns either side of those. `rows` slices `items` into rows, each sorted by
+/// column, so a binary search finds where to start.
+fn around<'a, T>(
+    items: &'a [T],
+    rows: &'a [Range<usize>],
+    row: usize,
+    columns: Range<usize>,
+    span: fn(&T) -> Range<usize>,
+) -> impl Iterator<Item = &'a T> + 'a {
+    let columns = columns.start.saturating_sub(1)..columns.end + 1;
+
+    rows[row.saturating_sub(1)..(row + 2).min(rows.len())]
+        .iter()
+        .flat_map(move |range| {
+            let row = &items[range.clone()];
+            let first = row.partition_point(|item| span(item).end <= columns.start);
+            let end = columns.end;
+
+            row[first..]
+                .iter()
+                .take_while(move |item| span(item).start < end)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u32> {
+        numbers.map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let schematic: Schematic = SAMPLE.parse().unwrap();
+
+        assert_eq!(
+            values(schematic.numbers().iter()),
+            [467, 114, 35, 633, 617, 58, 592, 755, 664, 598]
+        );
+        assert_eq!(
+            schematic.numbers()[3],
+            Number {
+                value: 633,
+                row: 2,
+                columns: 6..9
+            }
+        );
+        assert_eq!(
+            schematic
+                .symbols()
+                .iter()
+                .map(|symbol| symbol.char)
+                .collect::<String>(),
+            "*#*+$*"
+        );
+        assert_eq!(schematic.symbols()[1].position, (3, 6));
+    }
+
+    #[test]
+    fn test_sample() {
+        let schematic: Schematic = SAMPLE.parse().unwrap();
+
+        assert_eq!(schematic.part_numbers().map(|n| n.value).sum::<u32>(), 4361);
+
+        let touching = schematic
+            .symbols()
+            .iter()
+            .map(|symbol| values(schematic.numbers_touching(symbol)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            touching,
+            [
+                vec![467, 35],
+                vec![633],
+                vec![617],
+                vec![592],
+                vec![664],
+                vec![755, 598]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_touching() {
+        // `123` touches `*` through all of its digits; `#` has numbers beside and below.
+        let schematic: Schematic = "123.\n.*..\n..#4\n.56.".parse().unwrap();
+        let [a, b, c] = [0, 1, 2].map(|i| &schematic.numbers()[i]);
+        let [star, hash] = [0, 1].map(|i| &schematic.symbols()[i]);
+
+        assert_eq!(values(schematic.numbers_touching(star)), [123]);
+        assert_eq!(values(schematic.numbers_touching(hash)), [4, 56]);
+        assert_eq!(schematic.symbols_touching(a).collect::<Vec<_>>(), [star]);
+        assert_eq!(schematic.symbols_touching(b).collect::<Vec<_>>(), [hash]);
+        assert_eq!(schematic.symbols_touching(c).collect::<Vec<_>>(), [hash]);
+    }
+
+    #[test]
+    fn test_edges() {
+        // Numbers running into the grid's edges, symbols right at them.
+        let schematic: Schematic = "12\n=.\n.3".parse().unwrap();
+
+        assert_eq!(values(schematic.part_numbers()), [12, 3]);
+        assert_eq!(
+            values(schematic.numbers_touching(&schematic.symbols()[0])),
+            [12, 3]
+        );
+    }
+
+    #[test]
+    fn test_not_touching() {
+        // One column short, either side, and two rows away.
+        let schematic: Schematic = "1.*.2\n.....\n..3..".parse().unwrap();
+
+        assert_eq!(schematic.part_numbers().count(), 0);
+        assert_eq!(
+            schematic.numbers_touching(&schematic.symbols()[0]).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "...\n..".parse::<Schematic>(),
+            Err(ParseSchematicError::InvalidGrid(ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                actual: 2
+            }))
+        );
+        assert_eq!(
+            "............\n.99999999999".parse::<Schematic>(),
+            Err(ParseSchematicError::NumberTooLarge((1, 1)))
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use std::{fmt, num::ParseIntError};
+
+/// One line of the pile: `Card <id>: <winning numbers> | <numbers you have>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub drawn: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    /// Not of the form `Card <id>: <numbers> | <numbers>`.
+    InvalidCard(String),
+    InvalidNumber(ParseIntError),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCard(line) => write!(f, "invalid card {line:?}"),
+            Self::InvalidNumber(err) => write!(f, "invalid number: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+impl From<ParseIntError> for ParseCardError {
+    fn from(err: ParseIntError) -> Self {
+        Self::InvalidNumber(err)
+    }
+}
+
+impl Card {
+    /// Parses a card with any amount of numbers, separated by any whitespace.
+    pub fn parse(line: &str) -> Result<Self, ParseCardError> {
+        let invalid = || ParseCardError::InvalidCard(line.to_owned());
+
+        let (id, numbers) = line
+            .strip_prefix("Card ")
+            .and_then(|line| line.split_once(':'))
+            .ok_or_else(invalid)?;
+        let (winning, drawn) = numbers.split_once('|').ok_or_else(invalid)?;
+
+        let parse = |numbers: &str| {
+            numbers
+                .split_ascii_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            id: id.trim_start().parse()?,
+            winning: parse(winning)?,
+            drawn: parse(drawn)?,
+        })
+    }
+
+    /// How many of the numbers drawn are winning ones.
+    pub fn matches(&self) -> usize {
+        // Quadratic complexity, but at low numbers (number of drawn cards is just
+        // 25), probably more performant than set operations.
+        self.drawn
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count()
+    }
+}
+
+/// Where numbers are in lines where they're all right-aligned in columns of the same
+/// width, like `Card   1: 98  6 | 85 15  7`. Cards laid out alike can be matched from
+/// their bytes alone: numbers are equal exactly if their columns are. Each column's
+/// bytes make up a `u64` key, winning ones kept on the stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    len: usize,
+    /// Byte offsets of the `:` and the `|`.
+    colon: usize,
+    bar: usize,
+    width: usize,
+    n_winning: usize,
+    n_drawn: usize,
+}
+
+impl Layout {
+    /// Widest column that fits a key.
+    const MAX_WIDTH: usize = 8;
+    /// Most winning numbers kept on the stack.
+    const MAX_WINNING: usize = 32;
+
+    /// The layout of `line`, if its numbers are aligned, at most
+    /// [`MAX_WIDTH`](Self::MAX_WIDTH) wide, and there are at most
+    /// [`MAX_WINNING`](Self::MAX_WINNING) winning ones.
+    pub fn detect(line: &str) -> Option<Self> {
+        let colon = line.find(':')?;
+        let bar = line.find('|')?;
+        // The first number ends its column: ` 41` has a width of 2, `   41` one of 4.
+        let after = line.get(colon + 2..)?;
+        let digits = after.find(|c: char| c.is_ascii_digit())?;
+        let width = digits + after[digits..].find(|c: char| !c.is_ascii_digit())?;
+        let step = width + 1;
+
+        // ` 41 48 | 83  6`: each number is preceded by a space, the bar by one more.
+        let winning = bar.checked_sub(colon + 2)?;
+        let drawn = line.len() - bar - 1;
+        if width > Self::MAX_WIDTH
+            || !winning.is_multiple_of(step)
+            || !drawn.is_multiple_of(step)
+            || winning / step > Self::MAX_WINNING
+        {
+            return None;
+        }
+
+        let layout = Self {
+            len: line.len(),
+            colon,
+            bar,
+            width,
+            n_winning: winning / step,
+            n_drawn: drawn / step,
+        };
+
+        layout.matches(line).map(|_| layout)
+    }
+
+    /// How many of the numbers drawn on card `line` are winning ones, or `None` if
+    /// it's not laid out like this. Doesn't allocate.
+    pub fn matches(&self, line: &str) -> Option<usize> {
+        let line = line.as_bytes();
+        if line.len() != self.len || line[self.colon] != b':' || line[self.bar] != b'|' {
+            return None;
+        }
+
+        let id = line.strip_prefix(b"Card ")?[..self.colon - 5].trim_ascii_start();
+        if id.is_empty() || !id.iter().all(u8::is_ascii_digit) || line[self.bar - 1] != b' ' {
+            return None;
+        }
+
+        let step = self.width + 1;
+        let mut winning = [0; Self::MAX_WINNING];
+        for (i, key) in winning[..self.n_winning].iter_mut().enumerate() {
+            *key = self.key(line, self.colon + 1 + i * step)?;
+        }
+        let winning = &winning[..self.n_winning];
+
+        let mut matches = 0;
+        for i in 0..self.n_drawn {
+            let drawn = self.key(line, self.bar + 1 + i * step)?;
+
+            if winning.contains(&drawn) {
+                matches += 1;
+            }
+        }
+
+        Some(matches)
+    }
+
+    /// Key of the column after the space at `start`, if it holds a number without
+    /// leading zeros: those would make equal numbers look different.
+    fn key(&self, line: &[u8], start: usize) -> Option<u64> {
+        let column = &line[start + 1..start + 1 + self.width];
+        let digits = column.trim_ascii_start();
+
+        let valid = line[start] == b' '
+            && !digits.is_empty()
+            && digits.iter().all(u8::is_ascii_digit)
+            && (digits[0] != b'0' || digits.len() == 1);
+
+        valid.then(|| column.iter().fold(0, |key, &b| key << 8 | u64::from(b)))
+    }
+}
+
+/// What a card with `matches` is worth: one point for the first, doubling for each
+/// after that. `None` if that doesn't fit.
+pub fn points(matches: usize) -> Option<u64> {
+    match matches {
+        0 => Some(0),
+        n => 1_u64.checked_shl(u32::try_from(n - 1).ok()?),
+    }
+}
+
+/// How many matches each card in `input` has, one card per line. Lines laid out like
+/// the first are matched by [`Layout`], any others are parsed as a [`Card`].
+pub fn matches(input: &str) -> impl Iterator<Item = Result<usize, ParseCardError>> + '_ {
+    let layout = input.lines().next().and_then(Layout::detect);
+
+    input.lines().map(
+        move |line| match layout.and_then(|layout| layout.matches(line)) {
+            Some(matches) => Ok(matches),
+            None => Card::parse(line).map(|card| card.matches()),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    #[test]
+    fn test_parse() {
+        let card = Card::parse("Card  12:  1 21 |  9  1 48 21").unwrap();
+
+        assert_eq!(card.id, 12);
+        assert_eq!(card.winning, [1, 21]);
+        assert_eq!(card.drawn, [9, 1, 48, 21]);
+        assert_eq!(card.matches(), 2);
+    }
+
+    #[rstest]
+    #[case("Crad 1: 1 | 1", ParseCardError::InvalidCard("Crad 1: 1 | 1".to_owned()))]
+    #[case("Card 1 1 | 1", ParseCardError::InvalidCard("Card 1 1 | 1".to_owned()))]
+    #[case("Card 1: 1 1", ParseCardError::InvalidCard("Card 1: 1 1".to_owned()))]
+    fn test_parse_errors(#[case] line: &str, #[case] expected: ParseCardError) {
+        assert_eq!(Card::parse(line), Err(expected));
+    }
+
+    #[rstest]
+    #[case("Card one: 1 | 1")]
+    #[case("Card 1: 1 | x")]
+    #[case("Card 1: 1 | -1")]
+    fn test_parse_invalid_numbers(#[case] line: &str) {
+        assert!(matches!(
+            Card::parse(line),
+            Err(ParseCardError::InvalidNumber(_))
+        ));
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(
+            matches(SAMPLE).collect::<Result<Vec<_>, _>>(),
+            Ok(vec![4, 2, 2, 1, 0, 0])
+        );
+    }
+
+    #[rstest]
+    #[case(0, Some(0))]
+    #[case(1, Some(1))]
+    #[case(4, Some(8))]
+    #[case(64, Some(1 << 63))]
+    #[case(65, None)]
+    fn test_points(#[case] matches: usize, #[case] expected: Option<u64>) {
+        assert_eq!(points(matches), expected);
+    }
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", Some((5, 8)))]
+    #[case("Card   1: 98 | 85  7 98", Some((1, 3)))]
+    #[case("Card 1:   41    1 |    1   14", Some((2, 2)))]
+    #[case("Card 1: 1 2 | 2 3 4", Some((2, 3)))]
+    #[case("Card 1:  1 | 100", None)]
+    #[case("Card 1: 1  2 | 1 2", None)]
+    #[case("Card 1: 01 |  1", None)]
+    fn test_detect(#[case] line: &str, #[case] expected: Option<(usize, usize)>) {
+        assert_eq!(
+            Layout::detect(line).map(|layout| (layout.n_winning, layout.n_drawn)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_detect_limits() {
+        let wide = "Card 1: 123456789 | 123456789";
+        let many = format!("Card 1:{} | 1", " 1".repeat(33));
+
+        assert_eq!(Layout::detect(wide), None);
+        assert_eq!(Layout::detect(&many), None);
+        // Those are still parsed, just not as fast.
+        assert_eq!(matches(wide).next(), Some(Ok(1)));
+        assert_eq!(matches(&many).next(), Some(Ok(1)));
+    }
+
+    #[rstest]
+    // Different widths.
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 191")]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24")]
+    // Same width, but not aligned.
+    #[case("Card 2: 13 32 20 16 61 |  61 30 68 82 17 32 2 19")]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24,19")]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 0x")]
+    #[case("Card x: 13 32 20 16 61 | 61 30 68 82 17 32 24 19")]
+    #[case("Cards : 13 32 20 16 61 | 61 30 68 82 17 32 24 19")]
+    fn test_layout_mismatch(#[case] line: &str) {
+        let layout = Layout::detect(SAMPLE.lines().next().unwrap()).unwrap();
+
+        assert_eq!(layout.matches(line), None);
+    }
+
+    #[test]
+    fn test_mixed_layouts() {
+        let input = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 | 61 30 68 82 17 32 24 19 13 100
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 1 2 x | 1";
+
+        assert_eq!(
+            matches(input).take(3).collect::<Result<Vec<_>, _>>(),
+            Ok(vec![4, 2, 2])
+        );
+        assert!(matches(input).nth(3).unwrap().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_layout_agrees(
+            winning in prop::collection::vec(0..1000_u32, 1..20),
+            drawn in prop::collection::vec(0..1000_u32, 1..40),
+            width in 3..6_usize,
+        ) {
+            let numbers = |numbers: &[u32]| {
+                numbers.iter().map(|n| format!(" {n:>width$}")).collect::<String>()
+            };
+            let line = format!("Card 1:{} |{}", numbers(&winning), numbers(&drawn));
+
+            let layout = Layout::detect(&line);
+            let card = Card::parse(&line).unwrap();
+
+            prop_assert!(layout.is_some());
+            prop_assert_eq!(layout.unwrap().matches(&line), Some(card.matches()));
+        }
+    }
+}
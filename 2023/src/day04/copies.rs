@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use num::{BigUint, One};
+
+/// How many cards there are in the end, given how many matches each original card
+/// has, in order. A card with `n` matches wins a copy of each of the `n` cards after
+/// it, once per copy of itself; wins past the last card are lost.
+///
+/// Copies still to be added to upcoming cards are kept in a window that's only as long
+/// as the most matches seen, however many cards there are. Counts can double from one
+/// card to the next, so they're big integers.
+pub fn total_cards(matches: impl IntoIterator<Item = usize>) -> BigUint {
+    // Copies won so far of the next cards, the current one first.
+    let mut window: VecDeque<BigUint> = VecDeque::new();
+    let mut total = BigUint::default();
+
+    for n in matches {
+        let copies = window.pop_front().unwrap_or_default() + BigUint::one();
+
+        if window.len() < n {
+            window.resize(n, BigUint::default());
+        }
+        for won in window.iter_mut().take(n) {
+            *won += &copies;
+        }
+
+        total += copies;
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[4, 2, 2, 1, 0, 0], 30_u32)]
+    #[case(&[], 0)]
+    #[case(&[0, 0, 0], 3)]
+    // Wins past the last card are lost.
+    #[case(&[5], 1)]
+    #[case(&[1, 3], 3)]
+    // 1, 2, 4, 8.
+    #[case(&[3, 2, 1, 0], 15)]
+    fn test_total_cards(#[case] matches: &[usize], #[case] expected: u32) {
+        assert_eq!(
+            total_cards(matches.iter().copied()),
+            BigUint::from(expected)
+        );
+    }
+
+    /// Copies of every card at once, as the puzzle tells it.
+    fn naive(matches: &[usize]) -> BigUint {
+        let mut copies = vec![BigUint::one(); matches.len()];
+
+        for (i, &n) in matches.iter().enumerate() {
+            let current = copies[i].clone();
+            for won in copies.iter_mut().skip(i + 1).take(n) {
+                *won += &current;
+            }
+        }
+
+        copies.into_iter().sum()
+    }
+
+    #[test]
+    fn test_unbounded() {
+        // Each card wins the next two: copies grow like Fibonacci numbers, far past any
+        // machine integer, with a window of two.
+        let matches = [2; 10_000];
+        let total = total_cards(matches);
+
+        assert!(total > BigUint::from(u128::MAX));
+        assert_eq!(total, naive(&matches));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_agrees_with_naive(matches in prop::collection::vec(0..12_usize, 0..200)) {
+            prop_assert_eq!(total_cards(matches.iter().copied()), naive(&matches));
+        }
+    }
+}
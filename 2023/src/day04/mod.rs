@@ -1,2 +1,4 @@
+pub mod card;
+pub mod copies;
 pub mod part1;
 pub mod part2;
@@ -1,4 +1,4 @@
-use crate::Challenge;
+use crate::{day04::card, Challenge};
 
 /// --- Day 4: Scratchcards ---
 ///
@@ -53,11 +53,11 @@ use crate::Challenge;
 /// points (1 for the first match, then doubled three times for each of the three
 /// matches after the first).
 ///
-///     Card 2 has two winning numbers (32 and 61), so it is worth 2 points.
-///     Card 3 has two winning numbers (1 and 21), so it is worth 2 points.
-///     Card 4 has one winning number (84), so it is worth 1 point.
-///     Card 5 has no winning numbers, so it is worth no points.
-///     Card 6 has no winning numbers, so it is worth no points.
+/// - Card 2 has two winning numbers (32 and 61), so it is worth 2 points.
+/// - Card 3 has two winning numbers (1 and 21), so it is worth 2 points.
+/// - Card 4 has one winning number (84), so it is worth 1 point.
+/// - Card 5 has no winning numbers, so it is worth no points.
+/// - Card 6 has no winning numbers, so it is worth no points.
 ///
 /// So, in this example, the Elf's pile of scratchcards is worth 13 points.
 ///
@@ -99,53 +99,11 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
-        let mut sum = 0;
-
-        for line in input {
-            const N_WINNING_NUMBERS: usize = if cfg!(debug_assertions) { 5 } else { 10 };
-            const N_DRAWN_NUMBERS: usize = if cfg!(debug_assertions) { 8 } else { 25 };
-
-            let mut winning_numbers = [0; N_WINNING_NUMBERS];
-            let mut drawn_numbers = [0; N_DRAWN_NUMBERS];
-
-            static FIRST_INDENT: usize = if cfg!(debug_assertions) { 8 } else { 10 };
-            static NUMBER_WIDTH: usize = 2;
-            static STEP: usize = NUMBER_WIDTH + ' '.len_utf8();
-            static SECOND_INDENT: usize = FIRST_INDENT + N_WINNING_NUMBERS * STEP + " | ".len() - 1;
-
-            for (i, n) in winning_numbers.iter_mut().enumerate() {
-                let start = FIRST_INDENT + i * STEP;
-                let end = start + NUMBER_WIDTH;
-                let l = line[start..end].trim_start();
-
-                *n = l.parse().unwrap();
-            }
-
-            for (i, n) in drawn_numbers.iter_mut().enumerate() {
-                let start = SECOND_INDENT + i * STEP;
-                let end = start + NUMBER_WIDTH;
-                let l = line[start..end].trim_start();
-
-                *n = l.parse().unwrap();
-            }
-
-            let mut n = 0;
-            // Quadratic complexity, but at low numbers (number of drawn cards is just
-            // 25), probably more performant than set operations, especially since all
-            // items are on the stack (hashes are on the heap). Untested hypothesis!
-            for drawn_number in drawn_numbers.iter() {
-                for winning_number in winning_numbers.iter() {
-                    if drawn_number == winning_number {
-                        n += 1;
-                    }
-                }
-            }
-
-            if n > 0 {
-                sum += u32::pow(2, n - 1);
-            }
-        }
+        let sum = card::matches(Self::input())
+            .try_fold(0_u64, |sum, matches| {
+                sum.checked_add(card::points(matches.unwrap())?)
+            })
+            .unwrap();
 
         sum.to_string()
     }
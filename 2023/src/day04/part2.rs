@@ -1,4 +1,7 @@
-use crate::Challenge;
+use crate::{
+    day04::{card, copies},
+    Challenge,
+};
 
 /// --- Part Two ---
 ///
@@ -65,59 +68,9 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input().lines();
-        let mut n_cards = [1; 4096]; // 🤔
-        let mut sum = 0;
+        let matches = card::matches(Self::input()).map(Result::unwrap);
 
-        for (i_card, line) in input.enumerate() {
-            const N_WINNING_NUMBERS: usize = if cfg!(debug_assertions) { 5 } else { 10 };
-            const N_DRAWN_NUMBERS: usize = if cfg!(debug_assertions) { 8 } else { 25 };
-
-            let mut winning_numbers = [0; N_WINNING_NUMBERS];
-            let mut drawn_numbers = [0; N_DRAWN_NUMBERS];
-
-            static FIRST_INDENT: usize = if cfg!(debug_assertions) { 8 } else { 10 };
-            static NUMBER_WIDTH: usize = 2;
-            static STEP: usize = NUMBER_WIDTH + ' '.len_utf8();
-            static SECOND_INDENT: usize = FIRST_INDENT + N_WINNING_NUMBERS * STEP + " | ".len() - 1;
-
-            for (i, n) in winning_numbers.iter_mut().enumerate() {
-                let start = FIRST_INDENT + i * STEP;
-                let end = start + NUMBER_WIDTH;
-                let l = line[start..end].trim_start();
-
-                *n = l.parse().unwrap();
-            }
-
-            for (i, n) in drawn_numbers.iter_mut().enumerate() {
-                let start = SECOND_INDENT + i * STEP;
-                let end = start + NUMBER_WIDTH;
-                let l = line[start..end].trim_start();
-
-                *n = l.parse().unwrap();
-            }
-
-            let mut n = 0;
-            // Quadratic complexity, but at low numbers (number of drawn cards is just
-            // 25), probably more performant than set operations, especially since all
-            // items are on the stack (hashes are on the heap). Untested hypothesis!
-            for drawn_number in drawn_numbers.iter() {
-                for winning_number in winning_numbers.iter() {
-                    if drawn_number == winning_number {
-                        n += 1;
-                    }
-                }
-            }
-
-            let n_current = n_cards[i_card];
-            for k in 1..(n + 1) {
-                n_cards[i_card + k] += n_current;
-            }
-
-            sum += n_current;
-        }
-
-        sum.to_string()
+        copies::total_cards(matches).to_string()
     }
 
     fn solution(&self) -> &'static str {
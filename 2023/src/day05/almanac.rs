@@ -0,0 +1,526 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    num::ParseIntError,
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::interval::{IntervalSet, PiecewiseShift, Shift};
+
+/// The Island Island Almanac: seeds, plus maps between categories.
+///
+/// Maps may be listed in any order. Categories and maps form a graph, which has to be
+/// a forest (no cycles, also not via maps going in opposite directions): that way,
+/// there's at most one way to get from one category to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub maps: Vec<Map>,
+}
+
+/// A single `from-to-to map:` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    pub from: String,
+    pub to: String,
+    pub shifts: PiecewiseShift<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseAlmanacError {
+    MissingSeeds,
+    InvalidHeader(String),
+    InvalidMapping(String),
+    /// Mapping's ends, or how far it shifts, don't fit an `i64`.
+    MappingOverflow(String),
+    /// End of a seed range, given by its start and length, doesn't fit an `i64`.
+    SeedRangeOverflow {
+        start: i64,
+        length: i64,
+    },
+    /// Two mappings of a map, given by its header, share source values.
+    OverlappingMappings(String),
+    InvalidNumber(ParseIntError),
+    /// Maps lead around in a circle, listed as the categories along the way (first and
+    /// last are the same).
+    Cycle(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnknownCategory(String),
+    /// No chain of maps leads from one category to the other.
+    Disconnected {
+        from: String,
+        to: String,
+    },
+    /// Going backwards requires the map to be a bijection, which it isn't.
+    NotInvertible {
+        from: String,
+        to: String,
+    },
+    /// Values, or how far they're shifted, don't fit an `i64` along the way.
+    Overflow {
+        from: String,
+        to: String,
+    },
+}
+
+impl fmt::Display for ParseAlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSeeds => write!(f, "missing `seeds:` line"),
+            Self::InvalidHeader(header) => write!(f, "invalid map header {header:?}"),
+            Self::InvalidMapping(line) => write!(f, "invalid mapping {line:?}"),
+            Self::MappingOverflow(line) => write!(f, "mapping {line:?} overflows"),
+            Self::SeedRangeOverflow { start, length } => {
+                write!(f, "seed range of {length} from {start} overflows")
+            }
+            Self::OverlappingMappings(header) => {
+                write!(f, "overlapping mappings in {header:?}")
+            }
+            Self::InvalidNumber(err) => write!(f, "invalid number: {err}"),
+            Self::Cycle(categories) => {
+                write!(f, "maps form a cycle: {}", categories.join(" - "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCategory(category) => write!(f, "unknown category {category:?}"),
+            Self::Disconnected { from, to } => {
+                write!(f, "no chain of maps leads from {from:?} to {to:?}")
+            }
+            Self::NotInvertible { from, to } => {
+                write!(f, "{from}-to-{to} map is not invertible")
+            }
+            Self::Overflow { from, to } => {
+                write!(f, "mapping from {from:?} to {to:?} overflows")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseAlmanacError {}
+
+impl std::error::Error for QueryError {}
+
+impl From<ParseIntError> for ParseAlmanacError {
+    fn from(err: ParseIntError) -> Self {
+        Self::InvalidNumber(err)
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = ParseAlmanacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (seeds, input) = s.split_once('\n').ok_or(ParseAlmanacError::MissingSeeds)?;
+        let seeds = seeds
+            .strip_prefix("seeds:")
+            .ok_or(ParseAlmanacError::MissingSeeds)?
+            .split_ascii_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+
+        let mut almanac = Self {
+            seeds,
+            maps: Vec::new(),
+        };
+
+        for map in input.split("\n\n") {
+            let map = map.trim();
+            let (header, map) = map.split_once('\n').unwrap_or((map, ""));
+
+            let (from, to) = header
+                .strip_suffix(" map:")
+                .and_then(|header| header.split_once("-to-"))
+                .ok_or_else(|| ParseAlmanacError::InvalidHeader(header.to_owned()))?;
+
+            // Every new map must connect two previously unconnected categories,
+            // otherwise there'd be two ways between them.
+            if let Some(path) = almanac.path(from, to) {
+                let mut cycle = vec![from.to_owned()];
+                cycle.extend(path.into_iter().map(|(i, forward)| {
+                    let map = &almanac.maps[i];
+                    if forward { &map.to } else { &map.from }.clone()
+                }));
+                cycle.push(from.to_owned());
+
+                return Err(ParseAlmanacError::Cycle(cycle));
+            }
+
+            let mut shifts = map
+                .lines()
+                .map(parse_shift)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // Would be ambiguous, which `PiecewiseShift` refuses.
+            shifts.retain(|shift| !shift.range.is_empty());
+            shifts.sort_unstable_by_key(|shift| shift.range.start);
+            if shifts
+                .windows(2)
+                .any(|pair| pair[0].range.end > pair[1].range.start)
+            {
+                return Err(ParseAlmanacError::OverlappingMappings(header.to_owned()));
+            }
+
+            almanac.maps.push(Map {
+                from: from.to_owned(),
+                to: to.to_owned(),
+                shifts: PiecewiseShift::new(shifts),
+            });
+        }
+
+        Ok(almanac)
+    }
+}
+
+/// Parses a `destination source width` line.
+fn parse_shift(mapping: &str) -> Result<Shift<i64>, ParseAlmanacError> {
+    let (destination, source, width) = mapping
+        .split_whitespace()
+        .collect_tuple()
+        .ok_or_else(|| ParseAlmanacError::InvalidMapping(mapping.to_owned()))?;
+
+    let destination: i64 = destination.parse()?;
+    let source: i64 = source.parse()?;
+    let width: i64 = width.parse()?;
+
+    let overflow = || ParseAlmanacError::MappingOverflow(mapping.to_owned());
+
+    // Destinations must fit as well, or shifting into them overflows.
+    destination.checked_add(width).ok_or_else(overflow)?;
+
+    Ok(Shift {
+        range: source..source.checked_add(width).ok_or_else(overflow)?,
+        by: destination.checked_sub(source).ok_or_else(overflow)?,
+    })
+}
+
+impl Almanac {
+    /// The seeds line, read as pairs of start and length.
+    pub fn seed_ranges(&self) -> Result<IntervalSet<i64>, ParseAlmanacError> {
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &length)| {
+                let end = start
+                    .checked_add(length)
+                    .ok_or(ParseAlmanacError::SeedRangeOverflow { start, length })?;
+
+                Ok(start..end)
+            })
+            .collect()
+    }
+
+    /// All category names, in order of first appearance.
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        self.maps
+            .iter()
+            .flat_map(|map| [map.from.as_str(), map.to.as_str()])
+            .unique()
+    }
+
+    /// All maps along the way from one category to another flattened into a single
+    /// one. Maps are walked backwards via their inverse if need be, so `humidity` to
+    /// `soil` works just as well.
+    ///
+    /// Build this once to answer many queries: points via [`PiecewiseShift::get`],
+    /// ranges via [`PiecewiseShift::apply`], both in logarithmic time.
+    pub fn map_between(&self, from: &str, to: &str) -> Result<PiecewiseShift<i64>, QueryError> {
+        for category in [from, to] {
+            if !self.categories().contains(&category) {
+                return Err(QueryError::UnknownCategory(category.to_owned()));
+            }
+        }
+
+        let path = self
+            .path(from, to)
+            .ok_or_else(|| QueryError::Disconnected {
+                from: from.to_owned(),
+                to: to.to_owned(),
+            })?;
+
+        let overflow = || QueryError::Overflow {
+            from: from.to_owned(),
+            to: to.to_owned(),
+        };
+
+        path.into_iter()
+            .try_fold(PiecewiseShift::new([]), |composed, (i, forward)| {
+                let map = &self.maps[i];
+
+                if forward {
+                    composed.then(&map.shifts).ok_or_else(overflow)
+                } else {
+                    let inverse =
+                        map.shifts
+                            .inverse()
+                            .ok_or_else(|| QueryError::NotInvertible {
+                                from: map.from.clone(),
+                                to: map.to.clone(),
+                            })?;
+
+                    composed.then(&inverse).ok_or_else(overflow)
+                }
+            })
+    }
+
+    /// Shorthand for the puzzle's question.
+    pub fn seed_to_location(&self) -> Result<PiecewiseShift<i64>, QueryError> {
+        self.map_between("seed", "location")
+    }
+
+    /// Breadth-first search for the maps leading from one category to another, as
+    /// their indices plus whether they're walked forwards. The path is unique, as the
+    /// graph is a forest.
+    fn path(&self, from: &str, to: &str) -> Option<Vec<(usize, bool)>> {
+        let mut edges: HashMap<&str, Vec<(usize, bool, &str)>> = HashMap::new();
+        for (i, map) in self.maps.iter().enumerate() {
+            edges.entry(&map.from).or_default().push((i, true, &map.to));
+            edges
+                .entry(&map.to)
+                .or_default()
+                .push((i, false, &map.from));
+        }
+
+        // How each category was first reached: previous category and map taken.
+        let mut reached: HashMap<&str, Option<(&str, usize, bool)>> = HashMap::new();
+        reached.insert(from, None);
+
+        let mut queue = VecDeque::from([from]);
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut path = Vec::new();
+
+                let mut current = to;
+                while let Some(Some((previous, i, forward))) = reached.get(current) {
+                    path.push((*i, *forward));
+                    current = previous;
+                }
+
+                path.reverse();
+                return Some(path);
+            }
+
+            for &(i, forward, next) in edges.get(category).into_iter().flatten() {
+                if !reached.contains_key(next) {
+                    reached.insert(next, Some((category, i, forward)));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("input/sample.txt");
+
+    fn sample() -> Almanac {
+        SAMPLE.parse().unwrap()
+    }
+
+    #[rstest]
+    #[case(79, 82)]
+    #[case(14, 43)]
+    #[case(55, 86)]
+    #[case(13, 35)]
+    #[case(82, 46)]
+    fn test_seed_to_location(#[case] seed: i64, #[case] location: i64) {
+        let almanac = sample();
+
+        assert_eq!(almanac.seed_to_location().unwrap().get(seed), location);
+        assert_eq!(
+            almanac
+                .map_between("location", "seed")
+                .unwrap()
+                .get(location),
+            seed
+        );
+    }
+
+    #[test]
+    fn test_composed_matches_stages() {
+        let almanac = sample();
+        let composed = almanac.seed_to_location().unwrap();
+
+        for seed in 0..200 {
+            let staged = almanac
+                .maps
+                .iter()
+                .fold(seed, |value, map| map.shifts.get(value));
+
+            assert_eq!(composed.get(seed), staged);
+        }
+    }
+
+    #[test]
+    fn test_seed_ranges() {
+        let almanac = sample();
+        let locations = almanac
+            .seed_to_location()
+            .unwrap()
+            .apply(&almanac.seed_ranges().unwrap());
+
+        assert_eq!(locations.min(), Some(46));
+    }
+
+    #[test]
+    fn test_any_order() {
+        let (seeds, maps) = SAMPLE.split_once("\n\n").unwrap();
+        let shuffled = format!(
+            "{seeds}\n\n{}",
+            maps.trim_end()
+                .split("\n\n")
+                .collect_vec()
+                .into_iter()
+                .rev()
+                .join("\n\n")
+        );
+
+        let almanac: Almanac = shuffled.parse().unwrap();
+
+        assert_eq!(almanac.seed_to_location().unwrap().get(79), 82);
+        assert_eq!(almanac.seed_to_location(), sample().seed_to_location());
+    }
+
+    #[test]
+    fn test_backwards() {
+        // Seed 79 has soil 81 and humidity 78, see puzzle description.
+        let almanac = sample();
+
+        assert_eq!(almanac.map_between("humidity", "soil").unwrap().get(78), 81);
+        assert_eq!(almanac.map_between("soil", "humidity").unwrap().get(81), 78);
+        assert_eq!(almanac.map_between("soil", "soil").unwrap().get(81), 81);
+    }
+
+    #[test]
+    fn test_unknown_category() {
+        assert_eq!(
+            sample().map_between("seed", "sand"),
+            Err(QueryError::UnknownCategory("sand".into()))
+        );
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let almanac: Almanac = "seeds: 1\n\na-to-b map:\n1 2 3\n\nc-to-d map:\n4 5 6"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            almanac.map_between("a", "d"),
+            Err(QueryError::Disconnected {
+                from: "a".into(),
+                to: "d".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_not_invertible() {
+        let almanac: Almanac = "seeds: 1\n\na-to-b map:\n10 0 5".parse().unwrap();
+
+        assert_eq!(
+            almanac.map_between("b", "a"),
+            Err(QueryError::NotInvertible {
+                from: "a".into(),
+                to: "b".into()
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("a-to-b map:\n\nb-to-c map:\n\nc-to-a map:", &["c", "b", "a", "c"])]
+    #[case("a-to-b map:\n\nb-to-a map:", &["b", "a", "b"])]
+    #[case("a-to-a map:", &["a", "a"])]
+    fn test_cycle(#[case] maps: &str, #[case] expected: &[&str]) {
+        assert_eq!(
+            format!("seeds: 1\n\n{maps}").parse::<Almanac>(),
+            Err(ParseAlmanacError::Cycle(
+                expected.iter().map(|&c| c.to_owned()).collect()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert_eq!(
+            "seeds: 1\n\nseed-soil map:\n1 2 3".parse::<Almanac>(),
+            Err(ParseAlmanacError::InvalidHeader("seed-soil map:".into()))
+        );
+    }
+
+    #[rstest]
+    #[case("10 0 5\n20 3 5")]
+    #[case("10 3 5\n20 0 5\n0 8 1")]
+    #[case("10 0 5\n20 0 1")]
+    fn test_overlapping_mappings(#[case] mappings: &str) {
+        assert_eq!(
+            format!("seeds: 1\n\na-to-b map:\n{mappings}").parse::<Almanac>(),
+            Err(ParseAlmanacError::OverlappingMappings("a-to-b map:".into()))
+        );
+    }
+
+    #[rstest]
+    #[case("0 9223372036854775807 1")]
+    #[case("-2 9223372036854775807 0")]
+    #[case("9223372036854775807 -1 1")]
+    #[case("9223372036854775807 0 5")]
+    fn test_mapping_overflow(#[case] mapping: &str) {
+        assert_eq!(
+            format!("seeds: 1\n\na-to-b map:\n{mapping}").parse::<Almanac>(),
+            Err(ParseAlmanacError::MappingOverflow(mapping.into()))
+        );
+    }
+
+    #[test]
+    fn test_composed_overflow() {
+        // Each map's shift fits, but together they move 1e19 up.
+        let almanac: Almanac = "\
+seeds: 1
+
+a-to-b map:
+0 -5000000000000000000 1
+
+b-to-c map:
+5000000000000000000 0 1"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            almanac.map_between("a", "c"),
+            Err(QueryError::Overflow {
+                from: "a".into(),
+                to: "c".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_seed_range_overflow() {
+        let almanac: Almanac = "seeds: 9223372036854775807 5\n\nseed-to-location map:"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            almanac.seed_ranges(),
+            Err(ParseAlmanacError::SeedRangeOverflow {
+                start: i64::MAX,
+                length: 5
+            })
+        );
+    }
+}
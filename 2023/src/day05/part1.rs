@@ -1,6 +1,4 @@
-use itertools::Itertools;
-
-use crate::Challenge;
+use crate::{day05::almanac::Almanac, Challenge};
 
 /// --- Day 5: If You Give A Seed A Fertilizer ---
 ///
@@ -189,52 +187,18 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input();
-        let (seeds, input) = input.split_once('\n').unwrap();
-        let seeds = seeds.strip_prefix("seeds:").unwrap();
-
-        let mut values: Vec<u32> = seeds
-            .split_ascii_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
-
-        let maps = input.split("\n\n");
-
-        for map in maps {
-            let (header, map) = map.trim_start().split_once('\n').unwrap();
-            let (_from, _to) = header
-                .strip_suffix(" map:")
-                .unwrap()
-                .split_once("-to-")
-                .unwrap();
-
-            // ⚠️ not sure this was specified in the challenge? Was necessary to reach
-            // the solution though. Again: linear search instead of `HashSet` or binary
-            // search, as for very few elements (~20) it's faster (here, approx. 10%
-            // from very rough tests).
-            let mut seen = Vec::new();
-
-            for mapping in map.lines() {
-                let (destination, source, distance) =
-                    mapping.split_whitespace().collect_tuple().unwrap();
-
-                let destination: u32 = destination.parse().unwrap();
-                let source: u32 = source.parse().unwrap();
-                let distance: u32 = distance.parse().unwrap();
-
-                let range = source..=source + distance;
-
-                for (i, value) in values.iter_mut().enumerate() {
-                    if range.contains(value) && !seen.contains(&i) {
-                        // We can update values in-place, as history isn't necessary.
-                        // Values mapping to nothing new are simply untouched.
-                        *value = destination + (*value - source);
-                        seen.push(i);
-                    }
-                }
-            }
-        }
-
-        values.iter().min().unwrap().to_string()
+        let almanac: Almanac = Self::input().parse().unwrap();
+
+        // Overkill for a handful of seeds, but the composed map answers any further
+        // queries in logarithmic time.
+        let seed_to_location = almanac.seed_to_location().unwrap();
+
+        almanac
+            .seeds
+            .iter()
+            .map(|&seed| seed_to_location.get(seed))
+            .min()
+            .unwrap()
+            .to_string()
     }
 }
@@ -1,11 +1,4 @@
-use itertools::Itertools;
-use std::{
-    cmp::Ordering::{Equal, Greater, Less},
-    collections::VecDeque,
-    ops::Range,
-};
-
-use crate::Challenge;
+use crate::{day05::almanac::Almanac, Challenge};
 
 /// --- Part Two ---
 ///
@@ -37,96 +30,6 @@ use crate::Challenge;
 /// initial seed numbers?
 pub struct Part {}
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Shift {
-    range: Range<usize>,
-    by: isize,
-}
-
-impl Shift {
-    fn start(&self) -> usize {
-        self.range.start
-    }
-
-    fn end(&self) -> usize {
-        self.range.end
-    }
-}
-
-type Ranges = Vec<Range<usize>>;
-type Shifts = Vec<Shift>;
-
-impl Part {}
-
-#[inline]
-fn shift_by(range: Range<usize>, by: isize) -> Range<usize> {
-    range.start.checked_add_signed(by).unwrap()..range.end.checked_add_signed(by).unwrap()
-}
-
-fn apply(ranges: Ranges, shifts: Shifts) -> Ranges {
-    let mut res = Vec::with_capacity(ranges.len()); // approx. size
-
-    let mut queue = VecDeque::from(ranges);
-
-    // While processing, ranges might be split into various pieces; these *new*, smaller
-    // pieces are added back for later processing, as other shifts might apply to them.
-    'q: while let Some(range) = queue.pop_front() {
-        for shift in &shifts {
-            match (
-                shift.start().cmp(&range.start),
-                shift.start().cmp(&range.end),
-                shift.end().cmp(&range.start),
-                shift.end().cmp(&range.end),
-            ) {
-                // Out of range: shift fully left of range
-                (_, _, Less | Equal, _) => continue,
-                // Out of range: shift fully right of range
-                (_, Greater | Equal, _, _) => continue,
-                // Shift fully contains range
-                (Less | Equal, _, _, Greater | Equal) => {
-                    res.push(shift_by(range.clone(), shift.by));
-                    continue 'q;
-                }
-                // Partial overlap: shift left of range
-                (Less | Equal, _, _, Less) => {
-                    let right = shift.end()..range.end;
-
-                    queue.push_back(right);
-
-                    res.push(shift_by(range.start..shift.end(), shift.by));
-                    continue 'q;
-                }
-                // Shift fully covered by range, splitting it three ways
-                (Greater, _, _, Less) => {
-                    let left = range.start..shift.start();
-                    let right = shift.end()..range.end;
-
-                    queue.push_back(left);
-                    queue.push_back(right);
-
-                    res.push(shift_by(shift.range.clone(), shift.by));
-
-                    continue 'q;
-                }
-                // Partial overlap: shift right of range
-                (Greater, _, _, Equal | Greater) => {
-                    let left = range.start..shift.start();
-
-                    queue.push_back(left);
-
-                    res.push(shift_by(shift.start()..range.end, shift.by));
-
-                    continue 'q;
-                }
-            }
-        }
-
-        // No shift applied, push range as-is
-        res.push(range.clone());
-    }
-    res
-}
-
 impl Challenge for Part {
     fn input() -> &'static str {
         #[cfg(debug_assertions)]
@@ -161,52 +64,15 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input();
-        let (seeds, input) = input.split_once('\n').unwrap();
-        let seeds = seeds.strip_prefix("seeds:").unwrap();
-
-        let mut values: Vec<Range<usize>> = seeds
-            .split_ascii_whitespace()
-            .chunks(2)
-            .into_iter()
-            .map(|chunk| {
-                let (start, length) = chunk.collect_tuple().unwrap();
-                let start: usize = start.parse().unwrap();
-                let length: usize = length.parse().unwrap();
-
-                start..start + length
-            })
-            .collect();
-
-        let maps = input.split("\n\n");
-
-        for map in maps {
-            let (header, map) = map.trim_start().split_once('\n').unwrap();
-            let (_from, _to) = header
-                .strip_suffix(" map:")
-                .unwrap()
-                .split_once("-to-")
-                .unwrap();
-
-            let mut shifts = Vec::new();
-            for shift in map.lines() {
-                let (destination, source, by) = shift.split_whitespace().collect_tuple().unwrap();
-
-                let destination: usize = destination.parse().unwrap();
-                let source: usize = source.parse().unwrap();
-                let width: usize = by.parse().unwrap();
-
-                let shift = Shift {
-                    range: source..source + width,
-                    by: (destination - source) as isize,
-                };
-
-                shifts.push(shift);
-            }
-
-            values = apply(values, shifts);
-        }
-
-        values.iter().map(|r| r.start).min().unwrap().to_string()
+        let almanac: Almanac = Self::input().parse().unwrap();
+
+        // Ranges might be split into various pieces, some shifted, some not.
+        almanac
+            .seed_to_location()
+            .unwrap()
+            .apply(&almanac.seed_ranges().unwrap())
+            .min()
+            .unwrap()
+            .to_string()
     }
 }
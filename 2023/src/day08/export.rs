@@ -0,0 +1,307 @@
+use std::fmt::Write;
+
+use ahash::AHashMap;
+use itertools::Itertools;
+
+use super::network::{is_end, is_start, Instruction, Network};
+
+/// Colours for annotated ghost cycles, recycled if there are more ghosts.
+const PALETTE: [&str; 6] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#9467bd", "#ff7f0e", "#17becf",
+];
+
+/// Renders a network as a graph, for looking at in Graphviz, Gephi, yEd and friends.
+///
+/// `A` and `Z` nodes are highlighted. Edges are labelled with the instruction taking
+/// them (`L`, `R`, or both, if both point to the same node).
+pub struct Export<'n, 'a> {
+    network: &'n Network<'a>,
+    /// Start nodes of all annotated ghosts.
+    ghosts: Vec<&'a str>,
+    /// Edge (as node plus instruction taken) to the indices of ghosts looping along it.
+    cycles: AHashMap<(&'a str, Instruction), Vec<usize>>,
+}
+
+/// An edge, merged if left and right lead to the same node.
+struct Edge<'a> {
+    from: &'a str,
+    to: &'a str,
+    label: &'static str,
+    ghosts: Vec<usize>,
+}
+
+impl<'n, 'a> Export<'n, 'a> {
+    pub fn new(network: &'n Network<'a>) -> Self {
+        Self {
+            network,
+            ghosts: Vec::new(),
+            cycles: AHashMap::new(),
+        }
+    }
+
+    /// Marks the edges every ghost loops along forever, given the network's
+    /// instructions. Use [`Network::with_instructions`] to try out other ones.
+    pub fn with_cycles(mut self) -> Self {
+        let instructions = self.network.instructions();
+
+        for start in self.network.starts() {
+            let ghost = self.network.ghost(start);
+            let index = self.ghosts.len();
+
+            for (step, &node) in ghost.path.iter().enumerate().skip(ghost.offset) {
+                let instruction = instructions[step % instructions.len()];
+                let ghosts = self.cycles.entry((node, instruction)).or_default();
+
+                if !ghosts.contains(&index) {
+                    ghosts.push(index);
+                }
+            }
+
+            self.ghosts.push(start);
+        }
+
+        self
+    }
+
+    /// Graphviz, for `dot -Tsvg`. Annotated cycles get their own colour per ghost.
+    pub fn dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+
+        for node in self.nodes() {
+            let style = if is_start(node) {
+                ", style=filled, fillcolor=palegreen"
+            } else if is_end(node) {
+                ", style=filled, fillcolor=salmon"
+            } else {
+                ""
+            };
+
+            writeln!(dot, "    \"{}\" [shape=box{style}];", escape_dot(node)).unwrap();
+        }
+
+        for edge in self.edges() {
+            write!(
+                dot,
+                "    \"{}\" -> \"{}\" [label={}",
+                escape_dot(edge.from),
+                escape_dot(edge.to),
+                edge.label
+            )
+            .unwrap();
+
+            if !edge.ghosts.is_empty() {
+                // Parallel colours draw one line per ghost.
+                let colors = edge
+                    .ghosts
+                    .iter()
+                    .map(|&ghost| PALETTE[ghost % PALETTE.len()])
+                    .join(":");
+                let tooltip = edge
+                    .ghosts
+                    .iter()
+                    .map(|&ghost| self.ghosts[ghost])
+                    .join(", ");
+
+                write!(
+                    dot,
+                    ", color=\"{colors}\", penwidth=2, tooltip=\"cycle of {}\"",
+                    escape_dot(&tooltip)
+                )
+                .unwrap();
+            }
+
+            dot.push_str("];\n");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// GraphML, with node `kind` (`start`, `end` or `node`), edge `instruction` and
+    /// edge `cycles` (start nodes of all ghosts looping along it) attributes.
+    pub fn graphml(&self) -> String {
+        let mut xml = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+            "  <key id=\"instruction\" for=\"edge\" attr.name=\"instruction\" attr.type=\"string\"/>\n",
+            "  <key id=\"cycles\" for=\"edge\" attr.name=\"cycles\" attr.type=\"string\"/>\n",
+            "  <graph id=\"network\" edgedefault=\"directed\">\n",
+        ));
+
+        for node in self.nodes() {
+            let kind = if is_start(node) {
+                "start"
+            } else if is_end(node) {
+                "end"
+            } else {
+                "node"
+            };
+
+            writeln!(
+                xml,
+                "    <node id=\"{}\"><data key=\"kind\">{kind}</data></node>",
+                escape_xml(node)
+            )
+            .unwrap();
+        }
+
+        for edge in self.edges() {
+            write!(
+                xml,
+                "    <edge source=\"{}\" target=\"{}\"><data key=\"instruction\">{}</data>",
+                escape_xml(edge.from),
+                escape_xml(edge.to),
+                edge.label
+            )
+            .unwrap();
+
+            if !edge.ghosts.is_empty() {
+                let cycles = edge
+                    .ghosts
+                    .iter()
+                    .map(|&ghost| self.ghosts[ghost])
+                    .join(",");
+                write!(xml, "<data key=\"cycles\">{}</data>", escape_xml(&cycles)).unwrap();
+            }
+
+            xml.push_str("</edge>\n");
+        }
+
+        xml.push_str("  </graph>\n</graphml>\n");
+        xml
+    }
+
+    /// Sorted, to keep output stable.
+    fn nodes(&self) -> Vec<&'a str> {
+        self.network.nodes().sorted_unstable().collect()
+    }
+
+    fn edges(&self) -> Vec<Edge<'a>> {
+        let ghosts = |node, instruction| {
+            self.cycles
+                .get(&(node, instruction))
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let mut edges = Vec::new();
+
+        for from in self.nodes() {
+            let left = self.network.next(from, Instruction::Left);
+            let right = self.network.next(from, Instruction::Right);
+
+            if left == right {
+                let mut both = ghosts(from, Instruction::Left);
+                both.extend(ghosts(from, Instruction::Right));
+                both.sort_unstable();
+                both.dedup();
+
+                edges.push(Edge {
+                    from,
+                    to: left,
+                    label: "LR",
+                    ghosts: both,
+                });
+            } else {
+                edges.push(Edge {
+                    from,
+                    to: left,
+                    label: "L",
+                    ghosts: ghosts(from, Instruction::Left),
+                });
+                edges.push(Edge {
+                    from,
+                    to: right,
+                    label: "R",
+                    ghosts: ghosts(from, Instruction::Right),
+                });
+            }
+        }
+
+        edges
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("input/sample2.txt");
+
+    #[test]
+    fn test_dot() {
+        let network = Network::parse(SAMPLE).unwrap();
+
+        assert_eq!(
+            Export::new(&network).dot(),
+            r#"digraph network {
+    "AAA" [shape=box, style=filled, fillcolor=palegreen];
+    "BBB" [shape=box];
+    "ZZZ" [shape=box, style=filled, fillcolor=salmon];
+    "AAA" -> "BBB" [label=LR];
+    "BBB" -> "AAA" [label=L];
+    "BBB" -> "ZZZ" [label=R];
+    "ZZZ" -> "ZZZ" [label=LR];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_dot_cycles() {
+        let network = Network::parse(SAMPLE).unwrap();
+
+        // Loops at ZZZ with the given instructions, but BBB <-> AAA with only `L`.
+        let dot = Export::new(&network).with_cycles().dot();
+        assert!(dot.contains(
+            r##""ZZZ" -> "ZZZ" [label=LR, color="#1f77b4", penwidth=2, tooltip="cycle of AAA"];"##
+        ));
+        assert!(dot.contains(r#""BBB" -> "AAA" [label=L];"#));
+
+        let network = network.with_instructions("L").unwrap();
+        let dot = Export::new(&network).with_cycles().dot();
+        assert!(dot.contains(r##""BBB" -> "AAA" [label=L, color="#1f77b4""##));
+        assert!(dot.contains(r#""ZZZ" -> "ZZZ" [label=LR];"#));
+    }
+
+    #[test]
+    fn test_graphml() {
+        let network = Network::parse("L\n\n1A = (2Z, 1A)\n2Z = (2Z, 2Z)").unwrap();
+
+        assert_eq!(
+            Export::new(&network).with_cycles().graphml(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="kind" for="node" attr.name="kind" attr.type="string"/>
+  <key id="instruction" for="edge" attr.name="instruction" attr.type="string"/>
+  <key id="cycles" for="edge" attr.name="cycles" attr.type="string"/>
+  <graph id="network" edgedefault="directed">
+    <node id="1A"><data key="kind">start</data></node>
+    <node id="2Z"><data key="kind">end</data></node>
+    <edge source="1A" target="2Z"><data key="instruction">L</data></edge>
+    <edge source="1A" target="1A"><data key="instruction">R</data></edge>
+    <edge source="2Z" target="2Z"><data key="instruction">LR</data><data key="cycles">1A</data></edge>
+  </graph>
+</graphml>
+"#
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape_dot(r#"a"b\"#), r#"a\"b\\"#);
+        assert_eq!(escape_xml("<a&\"b\">"), "&lt;a&amp;&quot;b&quot;&gt;");
+    }
+}
@@ -0,0 +1,89 @@
+use ahash::AHashMap;
+
+/// Hands out dense ids for labels, in order of first appearance, so that anything
+/// keyed by label can live in a plain `Vec` instead. Labels may be of any width.
+///
+/// Ids are `u16`: real inputs have fewer than a thousand nodes, and halving the size of
+/// adjacency tables keeps more of them in cache.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Interner<'a> {
+    ids: AHashMap<&'a str, u16>,
+    labels: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `label`, handing out the next free one if it's new. `None` once all
+    /// ids are taken.
+    pub fn intern(&mut self, label: &'a str) -> Option<u16> {
+        if let Some(&id) = self.ids.get(label) {
+            return Some(id);
+        }
+
+        let id = u16::try_from(self.labels.len()).ok()?;
+        self.ids.insert(label, id);
+        self.labels.push(label);
+
+        Some(id)
+    }
+
+    pub fn get(&self, label: &str) -> Option<u16> {
+        self.ids.get(label).copied()
+    }
+
+    /// Panics if `id` wasn't handed out by this interner.
+    pub fn label(&self, id: u16) -> &'a str {
+        self.labels[usize::from(id)]
+    }
+
+    /// All labels, indexed by their id.
+    pub fn labels(&self) -> &[&'a str] {
+        &self.labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = Interner::new();
+
+        assert_eq!(interner.intern("AAA"), Some(0));
+        assert_eq!(interner.intern("a much longer label"), Some(1));
+        assert_eq!(interner.intern("AAA"), Some(0));
+        assert_eq!(interner.intern(""), Some(2));
+
+        assert_eq!(interner.get("a much longer label"), Some(1));
+        assert_eq!(interner.get("BBB"), None);
+        assert_eq!(interner.label(2), "");
+        assert_eq!(interner.labels(), ["AAA", "a much longer label", ""]);
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let labels = (0..=u16::MAX as usize + 1)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>();
+        let mut interner = Interner::new();
+
+        for label in &labels[..=u16::MAX as usize] {
+            assert!(interner.intern(label).is_some());
+        }
+
+        assert_eq!(interner.intern(&labels[u16::MAX as usize + 1]), None);
+        assert_eq!(interner.intern("0"), Some(0));
+    }
+}
@@ -1,2 +1,7 @@
+pub mod export;
+pub mod interner;
+pub mod network;
 pub mod part1;
 pub mod part2;
+pub mod schedule;
+pub mod validate;
@@ -0,0 +1,459 @@
+use std::fmt;
+
+use super::{
+    interner::Interner,
+    schedule::{Overflow, Schedule},
+};
+
+/// The maps: left/right instructions, plus a network of labelled nodes. Labels are
+/// borrowed from the input.
+///
+/// Nodes are interned into dense ids, so walking is plain indexing into a flat
+/// adjacency table, no hashing involved. Labelled methods are there for convenience,
+/// the `_id` ones for speed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network<'a> {
+    instructions: Vec<Instruction>,
+    labels: Interner<'a>,
+    /// Left and right successor of each node, indexed by id and [`Instruction`].
+    adjacency: Vec<[u16; 2]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    Left = 0,
+    Right = 1,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNetworkError {
+    MissingInstructions,
+    InvalidInstruction(char),
+    InvalidNode(String),
+    /// A node points to a node which isn't defined anywhere.
+    UnknownNode(String),
+    /// More nodes than fit into `u16` ids.
+    TooManyNodes,
+}
+
+impl fmt::Display for ParseNetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingInstructions => write!(f, "missing left/right instructions"),
+            Self::InvalidInstruction(c) => write!(f, "invalid instruction {c:?}"),
+            Self::InvalidNode(line) => write!(f, "invalid node {line:?}"),
+            Self::UnknownNode(label) => write!(f, "unknown node {label:?}"),
+            Self::TooManyNodes => write!(f, "more than {} nodes", u16::MAX as usize + 1),
+        }
+    }
+}
+
+impl std::error::Error for ParseNetworkError {}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Self, ParseNetworkError> {
+        let (instructions, nodes) = input
+            .split_once("\n\n")
+            .ok_or(ParseNetworkError::MissingInstructions)?;
+
+        let instructions = parse_instructions(instructions)?;
+
+        let mut labels = Interner::new();
+        let mut adjacency = Vec::new();
+        let mut defined = Vec::new();
+
+        for line in nodes.lines() {
+            let (node, left, right) = line
+                .split_once(" = ")
+                .and_then(|(node, targets)| {
+                    let (left, right) = targets
+                        .strip_prefix('(')?
+                        .strip_suffix(')')?
+                        .split_once(", ")?;
+
+                    Some((node, left, right))
+                })
+                .ok_or_else(|| ParseNetworkError::InvalidNode(line.to_owned()))?;
+
+            // Targets might show up before their own definition.
+            let [node, left, right] = [node, left, right]
+                .map(|label| labels.intern(label).ok_or(ParseNetworkError::TooManyNodes));
+            let (node, left, right) = (usize::from(node?), left?, right?);
+
+            if adjacency.len() < labels.len() {
+                adjacency.resize(labels.len(), [0; 2]);
+                defined.resize(labels.len(), false);
+            }
+
+            adjacency[node] = [left, right];
+            defined[node] = true;
+        }
+
+        // Checking once here means walking never fails later on.
+        if let Some(id) = defined.iter().position(|&defined| !defined) {
+            return Err(ParseNetworkError::UnknownNode(
+                labels.labels()[id].to_owned(),
+            ));
+        }
+
+        Ok(Self {
+            instructions,
+            labels,
+            adjacency,
+        })
+    }
+
+    /// The same network, navigated by different instructions.
+    pub fn with_instructions(&self, instructions: &str) -> Result<Self, ParseNetworkError> {
+        Ok(Self {
+            instructions: parse_instructions(instructions)?,
+            ..self.clone()
+        })
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub fn contains(&self, node: &str) -> bool {
+        self.labels.get(node).is_some()
+    }
+
+    /// All nodes, in order of first appearance.
+    pub fn nodes(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.labels.labels().iter().copied()
+    }
+
+    /// Where ghosts start: all nodes ending in `A`, sorted.
+    pub fn starts(&self) -> Vec<&'a str> {
+        let mut starts = self
+            .nodes()
+            .filter(|node| is_start(node))
+            .collect::<Vec<_>>();
+        starts.sort_unstable();
+
+        starts
+    }
+
+    pub fn id(&self, node: &str) -> Option<u16> {
+        self.labels.get(node)
+    }
+
+    pub fn label(&self, id: u16) -> &'a str {
+        self.labels.label(id)
+    }
+
+    /// The node reached from `node` following `instruction`.
+    ///
+    /// Panics if `node` isn't part of the network.
+    pub fn next(&self, node: &str, instruction: Instruction) -> &'a str {
+        let id = self.id(node).expect("node is part of the network");
+
+        self.label(self.next_id(id, instruction))
+    }
+
+    #[inline]
+    pub fn next_id(&self, id: u16, instruction: Instruction) -> u16 {
+        self.adjacency[usize::from(id)][instruction as usize]
+    }
+
+    /// Follows the instructions (repeating forever) from `start`, yielding every node
+    /// visited, `start` included.
+    pub fn walk(&self, start: &'a str) -> impl Iterator<Item = &'a str> + '_ {
+        let start = self.id(start).expect("node is part of the network");
+
+        self.walk_ids(start).map(|id| self.label(id))
+    }
+
+    pub fn walk_ids(&self, start: u16) -> impl Iterator<Item = u16> + '_ {
+        let mut instructions = self.instructions.iter().cycle();
+
+        std::iter::successors(Some(start), move |&id| {
+            Some(self.next_id(id, *instructions.next().unwrap()))
+        })
+    }
+
+    /// Walks from `start` until the ghost provably loops.
+    ///
+    /// A ghost's state is its node *plus* its position within the instructions. That
+    /// state only repeats at the same instruction position, so it suffices to remember
+    /// nodes at the start of each round of instructions: at most one entry per node.
+    pub fn ghost(&self, start: &'a str) -> Ghost<'a> {
+        let start = self.id(start).expect("node is part of the network");
+        let rounds = self.instructions.len();
+        let mut seen = vec![None; self.adjacency.len()];
+        let mut path = Vec::new();
+
+        for (step, id) in self.walk_ids(start).enumerate() {
+            if step.is_multiple_of(rounds) {
+                if let Some(offset) = seen[usize::from(id)].replace(step) {
+                    return Ghost { path, offset };
+                }
+            }
+
+            path.push(self.label(id));
+        }
+
+        unreachable!("walks are infinite")
+    }
+
+    /// Every step the ghost from `start` stands on a `Z` node.
+    pub fn schedule(&self, start: &'a str) -> Schedule {
+        self.ghost(start).schedule()
+    }
+
+    /// Steps until all ghosts, starting at all `A` nodes at once, stand on `Z` nodes.
+    /// `Ok(None)` if that never happens, an error if it happens past `u64::MAX` steps
+    /// (or can't be told not to).
+    ///
+    /// Works for any network, unlike the LCM in part 2, at the cost of walking every
+    /// ghost around its loop in full.
+    pub fn ghost_steps(&self) -> Result<Option<u64>, Overflow> {
+        let mut schedules = self.starts().into_iter().map(|start| self.schedule(start));
+
+        let Some(first) = schedules.next() else {
+            return Ok(None);
+        };
+
+        Ok(schedules
+            .try_fold(first, |all, schedule| all.intersect(&schedule))?
+            .first())
+    }
+}
+
+fn parse_instructions(instructions: &str) -> Result<Vec<Instruction>, ParseNetworkError> {
+    let instructions = instructions
+        .trim()
+        .chars()
+        .map(|c| match c {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            _ => Err(ParseNetworkError::InvalidInstruction(c)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if instructions.is_empty() {
+        return Err(ParseNetworkError::MissingInstructions);
+    }
+
+    Ok(instructions)
+}
+
+/// A ghost's walk up to the point where it provably repeats: `path[offset..]` is a
+/// loop, walked forever after, and always at the same instruction positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ghost<'a> {
+    pub path: Vec<&'a str>,
+    pub offset: usize,
+}
+
+impl<'a> Ghost<'a> {
+    pub fn tail(&self) -> &[&'a str] {
+        &self.path[..self.offset]
+    }
+
+    pub fn cycle(&self) -> &[&'a str] {
+        &self.path[self.offset..]
+    }
+
+    pub fn schedule(&self) -> Schedule {
+        let hits = self
+            .path
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| is_end(node))
+            .map(|(step, _)| step as u64);
+
+        Schedule::new(hits, self.offset as u64, self.cycle().len() as u64)
+    }
+}
+
+pub fn is_start(node: &str) -> bool {
+    node.ends_with('A')
+}
+
+pub fn is_end(node: &str) -> bool {
+    node.ends_with('Z')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_sample() {
+        let network = Network::parse(include_str!("input/sample3.txt")).unwrap();
+
+        assert_eq!(network.starts(), ["11A", "22A"]);
+        assert_eq!(network.ghost_steps(), Ok(Some(6)));
+    }
+
+    #[test]
+    fn test_input() {
+        let network = Network::parse(include_str!("input/1.txt")).unwrap();
+
+        assert_eq!(network.ghost_steps(), Ok(Some(18215611419223)));
+    }
+
+    #[test]
+    fn test_walk() {
+        let network = Network::parse(include_str!("input/sample2.txt")).unwrap();
+
+        assert_eq!(
+            network.walk("AAA").take(7).collect::<Vec<_>>(),
+            ["AAA", "BBB", "AAA", "BBB", "AAA", "BBB", "ZZZ"]
+        );
+    }
+
+    #[test]
+    fn test_breaks_lcm_assumptions() {
+        // AAA passes ZZZ before looping, then hits CCZ every third step, at alternating
+        // instruction positions. QQA hits SSZ at every even step. LCM of the first hits
+        // (1 and 2) would answer 2.
+        let network = Network::parse(
+            "LR
+
+AAA = (ZZZ, ZZZ)
+ZZZ = (BBB, BBB)
+BBB = (CCZ, CCZ)
+CCZ = (DDD, DDD)
+DDD = (BBB, BBB)
+QQA = (RRR, RRR)
+RRR = (SSZ, SSZ)
+SSZ = (RRR, RRR)",
+        )
+        .unwrap();
+
+        assert_eq!(network.ghost_steps(), Ok(Some(6)));
+        assert_eq!(network.ghost_steps(), Ok(brute_force(&network, 100)));
+    }
+
+    #[test]
+    fn test_offsets() {
+        // `1Z` at odd steps, `2Z` at steps 2 more than a multiple of three.
+        let network = Network::parse(
+            "L\n\n1A = (1Z, 1Z)\n1Z = (1A, 1A)\n2A = (2B, 2B)\n2B = (2Z, 2Z)\n2Z = (2A, 2A)",
+        )
+        .unwrap();
+        assert_eq!(network.ghost_steps(), Ok(Some(5)));
+
+        // `1Z` at odd steps, `2Z` at even ones.
+        let network = Network::parse(
+            "L\n\n1A = (1Z, 1Z)\n1Z = (1A, 1A)\n2A = (2B, 2B)\n2B = (2Z, 2Z)\n2Z = (2B, 2B)",
+        )
+        .unwrap();
+        assert_eq!(network.ghost_steps(), Ok(None));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Seven ghosts on prime cycles, each hitting its `Z` right before its `A`. They
+        // all meet after 1009 · 1013 · … · 1039 - 1 steps, about 1.18e21.
+        let mut input = String::from("L\n");
+        for period in [1009, 1013, 1019, 1021, 1031, 1033, 1039] {
+            let label = |k: u32| match k % period {
+                0 => format!("{period}A"),
+                k if k == period - 1 => format!("{period}Z"),
+                k => format!("{period}-{k}"),
+            };
+
+            for k in 0..period {
+                let next = label(k + 1);
+                input += &format!("\n{} = ({next}, {next})", label(k));
+            }
+        }
+        let network = Network::parse(&input).unwrap();
+
+        assert_eq!(network.starts().len(), 7);
+        assert_eq!(network.ghost_steps(), Err(Overflow));
+    }
+
+    #[test]
+    fn test_long_labels() {
+        let network = Network::parse(
+            "RL\n\nstart-A = (loop, end-Z)\nloop = (loop, loop)\nend-Z = (end-Z, end-Z)",
+        )
+        .unwrap();
+
+        assert_eq!(network.next("start-A", Instruction::Right), "end-Z");
+        assert_eq!(network.ghost_steps(), Ok(Some(1)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Network::parse("AAA = (AAA, AAA)"),
+            Err(ParseNetworkError::MissingInstructions)
+        );
+        assert_eq!(
+            Network::parse("LRX\n\nAAA = (AAA, AAA)"),
+            Err(ParseNetworkError::InvalidInstruction('X'))
+        );
+        assert_eq!(
+            Network::parse("L\n\nAAA = (AAA, BBB)"),
+            Err(ParseNetworkError::UnknownNode("BBB".into()))
+        );
+        assert_eq!(
+            Network::parse("L\n\nAAA = AAA, AAA"),
+            Err(ParseNetworkError::InvalidNode("AAA = AAA, AAA".into()))
+        );
+    }
+
+    /// Walks all ghosts in lockstep for a bounded number of steps.
+    fn brute_force(network: &Network, limit: usize) -> Option<u64> {
+        let mut walks = network
+            .starts()
+            .into_iter()
+            .map(|start| network.walk(start))
+            .collect::<Vec<_>>();
+
+        (0..limit)
+            .find(|_| {
+                walks
+                    .iter_mut()
+                    .map(|walk| walk.next().unwrap())
+                    .fold(true, |all, node| all & is_end(node))
+            })
+            .map(|step| step as u64)
+    }
+
+    prop_compose! {
+        /// Small random networks, labelled `<index><A|Z|X>`.
+        fn networks()(size in 2..8_usize)(
+            instructions in prop::collection::vec(prop::bool::ANY, 1..5),
+            kinds in prop::collection::vec(prop::sample::select(vec!['A', 'Z', 'X']), size),
+            targets in prop::collection::vec((0..size, 0..size), size),
+        ) -> String {
+            let label = |i: usize| format!("{i}{}", kinds[i]);
+
+            let mut input = instructions
+                .iter()
+                .map(|&left| if left { 'L' } else { 'R' })
+                .collect::<String>();
+            input.push('\n');
+
+            for (i, &(left, right)) in targets.iter().enumerate() {
+                input.push_str(&format!("\n{} = ({}, {})", label(i), label(left), label(right)));
+            }
+
+            input
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(input in networks()) {
+            let network = Network::parse(&input).unwrap();
+            prop_assume!(!network.starts().is_empty());
+
+            let expected = brute_force(&network, 10_000);
+
+            // Combined loops can get longer than brute force is willing to walk.
+            match network.ghost_steps().unwrap() {
+                Some(steps) if steps >= 10_000 => prop_assert_eq!(expected, None),
+                steps => prop_assert_eq!(steps, expected),
+            }
+        }
+    }
+}
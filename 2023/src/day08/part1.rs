@@ -1,5 +1,5 @@
+use super::network::Network;
 use crate::Challenge;
-use ahash::AHashMap;
 
 /// --- Day 8: Haunted Wasteland ---
 ///
@@ -57,12 +57,6 @@ use ahash::AHashMap;
 /// reach ZZZ?
 pub struct Part {}
 
-pub(super) fn hash(string: &str) -> u32 {
-    let mut bytes = [0; 4];
-    bytes[1..].copy_from_slice(string.as_bytes());
-    u32::from_le_bytes(bytes)
-}
-
 impl Challenge for Part {
     fn input() -> &'static str {
         #[cfg(debug_assertions)]
@@ -78,52 +72,26 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input();
-        let (directions, input) = input.split_once("\n\n").unwrap();
-        let directions = directions.chars().cycle();
-
         // Default performance (vanilla `HashMap` and inserting `&str`) found to be
         // 350µs. Using vanilla `ahash` was 260µs. Collecting all lines, then sorting,
         // then binary searching, was found to sit at 550µs. Writing the most simple
         // hash function (`&str` -> `u32`) sits at 210µs. *Combining* `ahash` and using
         // `u32` keys from the hash function sits at 190µs.
-        let mut map = AHashMap::new();
-
-        for line in input.lines() {
-            let (node, targets) = line.split_once(" = ").unwrap();
-
-            let (left, right) = targets
-                .strip_prefix('(')
-                .unwrap()
-                .strip_suffix(')')
-                .unwrap()
-                .split_once(", ")
-                .unwrap();
-
-            map.insert(hash(node), (left, right));
-        }
+        //
+        // Interning labels into dense ids, then walking a flat adjacency table, only
+        // hashes while parsing. `cargo bench --bench day08` compares it to the 190µs
+        // approach.
+        let network = Network::parse(Self::input()).unwrap();
 
         const START: &str = "AAA";
         const TARGET: &str = "ZZZ";
 
-        let mut n = 0;
-        let mut node = START;
-
-        for direction in directions {
-            if node == TARGET {
-                break;
-            }
-
-            let (left, right) = map.get(&hash(node)).unwrap();
+        let target = network.id(TARGET).unwrap();
 
-            match direction {
-                'L' => node = left,
-                'R' => node = right,
-                _ => unreachable!(),
-            }
-
-            n += 1;
-        }
+        let n = network
+            .walk_ids(network.id(START).unwrap())
+            .position(|node| node == target)
+            .unwrap();
 
         n.to_string()
     }
@@ -1,5 +1,5 @@
+use super::network::{is_end, Network};
 use crate::Challenge;
-use std::{sync::mpsc::channel, thread};
 
 /// --- Part Two ---
 ///
@@ -66,75 +66,18 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input();
-        let (directions, input) = input.split_once("\n\n").unwrap();
-
-        // `evmap` and vanilla threads doesn't lead to performance increases compared to
-        // standard `HashMap` with `rayon` and its `par_iter` *in this case*, but a fun
-        // exercise... In total, parallelization (with either approach) drops execution
-        // time from 1.8ms to 1.2ms.
-        let (map_rh, mut map_wh) = evmap::new();
-
-        let mut start_nodes = Vec::new();
-        for line in input.lines() {
-            let (node, targets) = line.split_once(" = ").unwrap();
-
-            let (left, right) = targets
-                .strip_prefix('(')
+        // `evmap` and vanilla threads, walking `&str` labels, used to drop execution
+        // time from 1.8ms to 1.2ms. Walking interned ids instead needs neither:
+        // `cargo bench --bench day08` compares it to walking hashed labels.
+        let network = Network::parse(Self::input()).unwrap();
+
+        // This walks every start node to its first end, aka `Z` node. That's all!
+        let distances = network.starts().into_iter().map(|start| {
+            network
+                .walk_ids(network.id(start).unwrap())
+                .position(|node| is_end(network.label(node)))
                 .unwrap()
-                .strip_suffix(')')
-                .unwrap()
-                .split_once(", ")
-                .unwrap();
-
-            if node.ends_with('A') {
-                start_nodes.push(node);
-            }
-
-            map_wh.insert(node, (left, right));
-        }
-
-        map_wh.refresh(); // Flush writes
-
-        let (sender, receiver) = channel();
-
-        let handles = start_nodes
-            .into_iter()
-            .map(|mut node| {
-                let rh = map_rh.clone();
-                let s = sender.clone();
-
-                thread::spawn(
-                    // This walks every start node to its first end, aka `Z` node.
-                    // That's all!
-                    move || {
-                        for (i, direction) in directions.chars().cycle().enumerate() {
-                            let read_guard = rh.get_one(node).unwrap();
-                            let (left, right) = (read_guard.0, read_guard.1);
-
-                            let next = match direction {
-                                'L' => left,
-                                'R' => right,
-                                _ => unreachable!(),
-                            };
-
-                            if next.ends_with('Z') {
-                                s.send(i + 1).unwrap();
-                                break;
-                            }
-
-                            node = next;
-                        }
-                    },
-                )
-            })
-            .collect::<Vec<_>>();
-
-        drop(sender); // Allow receiver loop to finish
-
-        for handle in handles {
-            handle.join().unwrap();
-        }
+        });
 
         // Why does LCM even work? The *general* case for this problem is incredibly
         // complex. It can only be solved trivially, aka with LCM, as the following
@@ -155,8 +98,12 @@ impl Challenge for Part {
         // Other observations:
         //
         // - paths can loop *before* ever encountering their first `Z` node
+        //
+        // `validate::Report` checks these against an input. Inputs breaking any of
+        // them need `network::Network::ghost_steps`, which walks every ghost around its
+        // entire loop and combines the loops via CRT.
         let mut least_common_multiple = 1;
-        for distance in receiver {
+        for distance in distances {
             least_common_multiple = num::integer::lcm(least_common_multiple, distance);
         }
 
@@ -0,0 +1,229 @@
+use std::fmt;
+
+use num::integer::{ExtendedGcd, Integer};
+
+/// The steps at which a ghost stands on a `Z` node: a few one-off hits before it
+/// settles into its loop, plus hits recurring with the loop's period.
+///
+/// Intersecting the schedules of all ghosts yields the steps at which *all* of them are
+/// on `Z` nodes at once. That's the general case of part 2, making none of the
+/// assumptions the plain LCM needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// Hits which never recur.
+    once: Vec<u64>,
+    period: u64,
+    /// Each of these recurs every `period` steps, starting at the given step.
+    starts: Vec<u64>,
+}
+
+/// Steps, or the period they recur with, don't fit a `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "steps overflow u64")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+impl Schedule {
+    /// From all hits seen until the loop closes: the loop spans `offset` (inclusive) up
+    /// to `offset + period` (exclusive), everything before that is a one-off.
+    pub fn new(hits: impl IntoIterator<Item = u64>, offset: u64, period: u64) -> Self {
+        assert!(period > 0, "period must be positive");
+
+        let (once, starts) = hits.into_iter().partition(|&step| step < offset);
+
+        Self {
+            once,
+            period,
+            starts,
+        }
+    }
+
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// Hits which never recur.
+    pub fn once(&self) -> &[u64] {
+        &self.once
+    }
+
+    /// Where recurring hits first occur.
+    pub fn starts(&self) -> &[u64] {
+        &self.starts
+    }
+
+    pub fn contains(&self, step: u64) -> bool {
+        self.once.contains(&step)
+            || self
+                .starts
+                .iter()
+                .any(|&start| step >= start && (step - start).is_multiple_of(self.period))
+    }
+
+    /// The earliest hit, if there's any at all.
+    pub fn first(&self) -> Option<u64> {
+        self.once.iter().chain(&self.starts).copied().min()
+    }
+
+    /// Steps found in both schedules. Fails if the combined period doesn't fit a
+    /// `u64`, or a recurring hit doesn't.
+    ///
+    /// Recurring hits are paired up via the (generalised, non-coprime) Chinese
+    /// Remainder Theorem, so the number of recurring hits multiplies. Real inputs have
+    /// a single one per ghost, keeping this cheap.
+    pub fn intersect(&self, other: &Self) -> Result<Self, Overflow> {
+        let mut once = self
+            .once
+            .iter()
+            .filter(|&&step| other.contains(step))
+            .chain(other.once.iter().filter(|&&step| self.contains(step)))
+            .copied()
+            .collect::<Vec<_>>();
+        once.sort_unstable();
+        once.dedup();
+
+        let period = (self.period / self.period.gcd(&other.period))
+            .checked_mul(other.period)
+            .ok_or(Overflow)?;
+
+        let mut starts = Vec::new();
+        for &a in &self.starts {
+            for &b in &other.starts {
+                if let Some(residue) = crt(a, self.period, b, other.period) {
+                    // Neither recurs before it starts, so neither does the combination.
+                    let earliest = a.max(b);
+                    let start = (earliest.saturating_sub(residue))
+                        .div_ceil(period)
+                        .checked_mul(period)
+                        .and_then(|wait| residue.checked_add(wait))
+                        .ok_or(Overflow)?;
+
+                    starts.push(start);
+                }
+            }
+        }
+        starts.sort_unstable();
+
+        Ok(Self {
+            once,
+            period,
+            starts,
+        })
+    }
+}
+
+/// Solves `x = a (mod m)` and `x = b (mod n)` for the smallest non-negative `x`, with
+/// `m` and `n` not necessarily coprime, and their LCM fitting a `u64`.
+fn crt(a: u64, m: u64, b: u64, n: u64) -> Option<u64> {
+    let (a, m, b, n) = (i128::from(a), i128::from(m), i128::from(b), i128::from(n));
+    let ExtendedGcd { gcd, x, .. } = m.extended_gcd(&n);
+
+    if (b - a) % gcd != 0 {
+        return None;
+    }
+
+    // `a + m * k` satisfies the first congruence for any `k`; pick `k` to satisfy the
+    // second one as well. Both factors are below `n / gcd`, a `u64`: their product
+    // only fits unsigned.
+    let modulus = n / gcd;
+    let factor = |value: i128| value.rem_euclid(modulus) as u128;
+    let k = (factor((b - a) / gcd) * factor(x) % modulus as u128) as i128;
+    let lcm = m / gcd * n;
+
+    // `m * k` is below the LCM.
+    Some((a + m * k).rem_euclid(lcm) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(2, 3, 3, 5, Some(8))]
+    #[case(1, 4, 3, 6, Some(9))]
+    #[case(1, 4, 2, 6, None)]
+    #[case(0, 7, 0, 7, Some(0))]
+    #[case(5, 7, 5, 7, Some(5))]
+    fn test_crt(
+        #[case] a: u64,
+        #[case] m: u64,
+        #[case] b: u64,
+        #[case] n: u64,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(crt(a, m, b, n), expected);
+    }
+
+    #[test]
+    fn test_contains() {
+        let schedule = Schedule::new([1, 5, 7], 4, 6);
+
+        assert_eq!(schedule.first(), Some(1));
+        assert!(schedule.contains(1));
+        assert!(!schedule.contains(3));
+        assert!(schedule.contains(5 + 6 * 3));
+        assert!(schedule.contains(7 + 6));
+        assert!(!schedule.contains(6));
+    }
+
+    #[test]
+    fn test_intersect_brute_force() {
+        let schedules = [
+            Schedule::new([0, 3, 10, 12], 9, 4),
+            Schedule::new([2, 12, 20], 11, 6),
+            Schedule::new([4, 8, 12], 5, 10),
+            Schedule::new([], 0, 3),
+        ];
+
+        for left in &schedules {
+            for right in &schedules {
+                let both = left.intersect(right).unwrap();
+
+                for step in 0..1000 {
+                    assert_eq!(
+                        both.contains(step),
+                        left.contains(step) && right.contains(step),
+                        "{left:?} and {right:?} at {step}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_intersect_waits_for_both() {
+        // Congruent at 2, but the second one only starts recurring at 14.
+        let left = Schedule::new([2], 0, 4);
+        let right = Schedule::new([14], 14, 6);
+
+        assert_eq!(left.intersect(&right).unwrap().first(), Some(14));
+    }
+
+    #[test]
+    fn test_intersect_overflow() {
+        // Seven coprime periods: their product is about 1.18e21.
+        let all = [1009, 1013, 1019, 1021, 1031, 1033, 1039]
+            .map(|period| Schedule::new([period - 1], 0, period))
+            .into_iter()
+            .map(Ok)
+            .reduce(|all, schedule| all?.intersect(&schedule?));
+
+        assert_eq!(all, Some(Err(Overflow)));
+    }
+
+    #[test]
+    fn test_crt_large() {
+        // Coprime periods just fitting a `u64` together.
+        let (m, n) = (4_294_967_291, 4_294_967_279);
+        let x = crt(m - 1, m, n - 1, n).unwrap();
+
+        assert_eq!(x, m * n - 1);
+    }
+}
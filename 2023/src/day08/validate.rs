@@ -0,0 +1,394 @@
+use std::fmt;
+
+use ahash::AHashMap;
+use itertools::Itertools;
+
+use super::{
+    network::{is_end, is_start, Instruction, Network},
+    schedule::{Overflow, Schedule},
+};
+
+/// The properties of the input which make the plain LCM in part 2 valid. See the
+/// comment there for the details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Assumption {
+    /// `Z` nodes are only ever reached at the start of a round of instructions, so
+    /// they're always left the same way.
+    EndsAtRoundStart,
+    /// Every ghost visits exactly one `Z` node, ever.
+    SingleEnd,
+    /// No two ghosts ever visit the same node.
+    Disjoint,
+    /// Reaching the `Z` node takes exactly as long as looping back to it, so the ghost
+    /// is there at multiples of that, and only then.
+    EndAtPeriod,
+    /// No node leads back to an `A` node.
+    StartsUnreachable,
+}
+
+impl Assumption {
+    pub const ALL: [Self; 5] = [
+        Self::EndsAtRoundStart,
+        Self::SingleEnd,
+        Self::Disjoint,
+        Self::EndAtPeriod,
+        Self::StartsUnreachable,
+    ];
+}
+
+impl fmt::Display for Assumption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::EndsAtRoundStart => "Z nodes are only reached at the start of a round",
+            Self::SingleEnd => "every ghost visits exactly one Z node",
+            Self::Disjoint => "ghosts never share nodes",
+            Self::EndAtPeriod => "reaching Z takes as long as looping back to it",
+            Self::StartsUnreachable => "A nodes have no incoming edges",
+        };
+
+        write!(f, "{description}")
+    }
+}
+
+/// A single counterexample to an [`Assumption`], naming the offending nodes. Ghosts
+/// are named by their start node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation<'a> {
+    EndOffRound {
+        start: &'a str,
+        end: &'a str,
+        step: usize,
+    },
+    /// All `Z` nodes the ghost visits, possibly none.
+    Ends {
+        start: &'a str,
+        ends: Vec<&'a str>,
+    },
+    Shared {
+        node: &'a str,
+        starts: [&'a str; 2],
+    },
+    Offset {
+        start: &'a str,
+        first: Option<u64>,
+        period: u64,
+    },
+    IncomingEdge {
+        from: &'a str,
+        start: &'a str,
+    },
+}
+
+impl Violation<'_> {
+    pub fn assumption(&self) -> Assumption {
+        match self {
+            Self::EndOffRound { .. } => Assumption::EndsAtRoundStart,
+            Self::Ends { .. } => Assumption::SingleEnd,
+            Self::Shared { .. } => Assumption::Disjoint,
+            Self::Offset { .. } => Assumption::EndAtPeriod,
+            Self::IncomingEdge { .. } => Assumption::StartsUnreachable,
+        }
+    }
+}
+
+impl fmt::Display for Violation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EndOffRound { start, end, step } => {
+                write!(f, "ghost from {start} reaches {end} after {step} steps")
+            }
+            Self::Ends { start, ends } if ends.is_empty() => {
+                write!(f, "ghost from {start} never reaches a Z node")
+            }
+            Self::Ends { start, ends } => {
+                write!(f, "ghost from {start} reaches {}", ends.join(", "))
+            }
+            Self::Shared {
+                node,
+                starts: [a, b],
+            } => write!(f, "ghosts from {a} and {b} both visit {node}"),
+            Self::Offset {
+                start,
+                first: Some(first),
+                period,
+            } => write!(
+                f,
+                "ghost from {start} first reaches Z after {first} steps, but loops every {period}"
+            ),
+            Self::Offset { start, .. } => write!(f, "ghost from {start} never reaches Z"),
+            Self::IncomingEdge { from, start } => write!(f, "{from} leads to {start}"),
+        }
+    }
+}
+
+/// Checks all [`Assumption`]s against a network, collecting every violation.
+///
+/// This walks every ghost around its loop in full, which is just as expensive as
+/// solving the general case. The point is diagnosing inputs, not speeding anything up.
+#[derive(Debug, Clone)]
+pub struct Report<'a> {
+    schedules: Vec<Schedule>,
+    violations: Vec<Violation<'a>>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(network: &Network<'a>) -> Self {
+        let rounds = network.instructions().len();
+        let mut violations = Vec::new();
+        let mut schedules = Vec::new();
+
+        // Node to the first ghost visiting it.
+        let mut visited = AHashMap::new();
+
+        for start in network.starts() {
+            let ghost = network.ghost(start);
+            let schedule = ghost.schedule();
+
+            for (step, &node) in ghost.path.iter().enumerate() {
+                if is_end(node) && !step.is_multiple_of(rounds) {
+                    violations.push(Violation::EndOffRound {
+                        start,
+                        end: node,
+                        step,
+                    });
+                }
+            }
+
+            let ends = ghost
+                .path
+                .iter()
+                .copied()
+                .filter(|node| is_end(node))
+                .unique()
+                .collect::<Vec<_>>();
+            if ends.len() != 1 {
+                violations.push(Violation::Ends { start, ends });
+            }
+
+            for &node in ghost.path.iter().unique() {
+                let first = *visited.entry(node).or_insert(start);
+                if first != start {
+                    violations.push(Violation::Shared {
+                        node,
+                        starts: [first, start],
+                    });
+                }
+            }
+
+            if !(schedule.once().is_empty() && schedule.starts() == [schedule.period()]) {
+                violations.push(Violation::Offset {
+                    start,
+                    first: schedule.first(),
+                    period: schedule.period(),
+                });
+            }
+
+            schedules.push(schedule);
+        }
+
+        for from in network.nodes().sorted_unstable() {
+            for instruction in [Instruction::Left, Instruction::Right] {
+                let to = network.next(from, instruction);
+                if is_start(to) {
+                    violations.push(Violation::IncomingEdge { from, start: to });
+                }
+            }
+        }
+
+        // Both sides pointing to the same `A` node would be reported twice.
+        violations.dedup();
+        violations.sort_by_key(Violation::assumption);
+
+        Self {
+            schedules,
+            violations,
+        }
+    }
+
+    pub fn violations(&self) -> &[Violation<'a>] {
+        &self.violations
+    }
+
+    pub fn holds(&self, assumption: Assumption) -> bool {
+        self.violations
+            .iter()
+            .all(|violation| violation.assumption() != assumption)
+    }
+
+    pub fn all_hold(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// The LCM shortcut of part 2, but refusing to answer if it's not valid. Fall back
+    /// to [`Network::ghost_steps`] then. Fails if the LCM doesn't fit a `u64`.
+    pub fn lcm(&self) -> Result<Option<u64>, Overflow> {
+        if !self.all_hold() {
+            return Ok(None);
+        }
+
+        let mut periods = self.schedules.iter().map(Schedule::period);
+        let Some(first) = periods.next() else {
+            return Ok(None);
+        };
+
+        periods
+            .try_fold(first, |lcm, period| {
+                (lcm / num::integer::gcd(lcm, period))
+                    .checked_mul(period)
+                    .ok_or(Overflow)
+            })
+            .map(Some)
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for assumption in Assumption::ALL {
+            let mark = if self.holds(assumption) { '✅' } else { '❌' };
+            writeln!(f, "{mark} {assumption}")?;
+
+            for violation in self
+                .violations
+                .iter()
+                .filter(|violation| violation.assumption() == assumption)
+            {
+                writeln!(f, "   - {violation}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input() {
+        let network = Network::parse(include_str!("input/1.txt")).unwrap();
+        let report = Report::new(&network);
+
+        assert_eq!(report.violations(), []);
+        assert_eq!(report.lcm(), Ok(Some(18215611419223)));
+    }
+
+    #[test]
+    fn test_sample() {
+        // Happens to give the right answer via LCM anyway.
+        let network = Network::parse(include_str!("input/sample3.txt")).unwrap();
+        let report = Report::new(&network);
+
+        assert_eq!(
+            report.violations(),
+            [
+                Violation::EndOffRound {
+                    start: "22A",
+                    end: "22Z",
+                    step: 3
+                },
+                Violation::Offset {
+                    start: "22A",
+                    first: Some(3),
+                    period: 6
+                },
+            ]
+        );
+        assert!(report.holds(Assumption::Disjoint));
+        assert!(!report.holds(Assumption::EndAtPeriod));
+        assert_eq!(report.lcm(), Ok(None));
+        assert_eq!(network.ghost_steps(), Ok(Some(6)));
+    }
+
+    #[test]
+    fn test_shared() {
+        let network = Network::parse(
+            "L
+
+1A = (XX, XX)
+2A = (XX, XX)
+XX = (1A, 1A)",
+        )
+        .unwrap();
+        let report = Report::new(&network);
+
+        assert_eq!(
+            report.violations(),
+            [
+                Violation::Ends {
+                    start: "1A",
+                    ends: vec![]
+                },
+                Violation::Ends {
+                    start: "2A",
+                    ends: vec![]
+                },
+                Violation::Shared {
+                    node: "XX",
+                    starts: ["1A", "2A"]
+                },
+                Violation::Shared {
+                    node: "1A",
+                    starts: ["1A", "2A"]
+                },
+                Violation::Offset {
+                    start: "1A",
+                    first: None,
+                    period: 2
+                },
+                Violation::Offset {
+                    start: "2A",
+                    first: None,
+                    period: 2
+                },
+                Violation::IncomingEdge {
+                    from: "XX",
+                    start: "1A"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lcm_overflow() {
+        // Seven ghosts on prime cycles, each looping back to the node after its `A` once
+        // it hits its `Z`. That's every assumption met, but an LCM of about 1.18e21.
+        let mut input = String::from("L\n");
+        for period in [1009, 1013, 1019, 1021, 1031, 1033, 1039] {
+            let label = |k: u32| match k {
+                0 => format!("{period}A"),
+                k if k == period => format!("{period}Z"),
+                k => format!("{period}-{k}"),
+            };
+
+            for k in 0..period {
+                let next = label(k + 1);
+                input += &format!("\n{} = ({next}, {next})", label(k));
+            }
+            input += &format!("\n{} = ({next}, {next})", label(period), next = label(1));
+        }
+        let network = Network::parse(&input).unwrap();
+        let report = Report::new(&network);
+
+        assert_eq!(report.violations(), []);
+        assert_eq!(report.lcm(), Err(Overflow));
+    }
+
+    #[test]
+    fn test_display() {
+        let network = Network::parse(include_str!("input/sample3.txt")).unwrap();
+
+        assert_eq!(
+            Report::new(&network).to_string(),
+            "\
+❌ Z nodes are only reached at the start of a round
+   - ghost from 22A reaches 22Z after 3 steps
+✅ every ghost visits exactly one Z node
+✅ ghosts never share nodes
+❌ reaching Z takes as long as looping back to it
+   - ghost from 22A first reaches Z after 3 steps, but loops every 6
+✅ A nodes have no incoming edges
+"
+        );
+    }
+}
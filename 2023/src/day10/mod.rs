@@ -1,2 +1,4 @@
 pub mod part1;
 pub mod part2;
+pub mod pipes;
+pub mod render;
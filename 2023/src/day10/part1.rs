@@ -1,6 +1,5 @@
-use itertools::Itertools;
-
-use crate::Challenge;
+use super::pipes::PipeLoop;
+use crate::{grid::Grid, Challenge};
 
 /// --- Day 10: Pipe Maze ---
 ///
@@ -167,85 +166,9 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let map = Self::input()
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec();
-
-        let start = map
-            .iter()
-            .enumerate()
-            .find_map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .find_map(|(j, &c)| if c == 'S' { Some((i, j)) } else { None })
-            })
-            .unwrap();
-
-        let mut pos = start;
-        let mut prev_pos = None;
-
-        let mut n = 0;
-
-        let mut possible = Vec::with_capacity(4);
-
-        loop {
-            let element = map[pos.0][pos.1];
-
-            if element == 'S' && prev_pos.is_some() {
-                break;
-            }
-
-            possible.clear();
-
-            if pos.1 > 0 {
-                let west = map[pos.0].get(pos.1 - 1);
-                if let Some(e) = west {
-                    if let ('-' | 'L' | 'F' | 'S', '-' | 'J' | '7' | 'S') = (e, element) {
-                        possible.push((pos.0, pos.1 - 1));
-                    }
-                }
-            }
-
-            let east = map[pos.0].get(pos.1 + 1);
-            if let Some(e) = east {
-                if let ('-' | 'J' | '7' | 'S', '-' | 'F' | 'L' | 'S') = (e, element) {
-                    possible.push((pos.0, pos.1 + 1));
-                }
-            }
-
-            if pos.0 > 0 {
-                let north_line = map.get(pos.0 - 1);
-                if let Some(north) = north_line {
-                    if let ('|' | 'F' | '7' | 'S', '|' | 'L' | 'J' | 'S') = (north[pos.1], element)
-                    {
-                        possible.push((pos.0 - 1, pos.1));
-                    }
-                }
-            }
-
-            let south_line = map.get(pos.0 + 1);
-            if let Some(south) = south_line {
-                if let ('|' | 'L' | 'J' | 'S', '|' | '7' | 'F' | 'S') = (south[pos.1], element) {
-                    possible.push((pos.0 + 1, pos.1));
-                }
-            }
-
-            n += 1;
-
-            let next = if let Some(pp) = prev_pos {
-                possible.iter().find(|&&pos| pos != pp).unwrap()
-            } else {
-                // We're at `S`: two are possible, and no previous available. Just pick
-                // one.
-                possible.first().unwrap()
-            };
-
-            prev_pos = Some(pos);
-            pos = *next;
-        }
+        let map: Grid<char> = Self::input().parse().unwrap();
 
-        (n / 2).to_string()
+        PipeLoop::find(&map).unwrap().farthest().to_string()
     }
 
     fn solution(&self) -> &'static str {
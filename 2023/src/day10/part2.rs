@@ -1,4 +1,5 @@
-use crate::Challenge;
+use super::pipes::PipeLoop;
+use crate::{grid::Grid, Challenge};
 
 /// --- Part Two ---
 ///
@@ -129,8 +130,10 @@ impl Challenge for Part {
     fn input() -> &'static str {
         #[cfg(debug_assertions)]
         {
-            include_str!("input/sample1.txt")
-            // include_str!("input/sample2.txt")
+            // include_str!("input/sample3.txt")
+            // include_str!("input/sample4.txt")
+            include_str!("input/sample5.txt")
+            // include_str!("input/sample6.txt")
         }
 
         #[cfg(not(debug_assertions))]
@@ -140,19 +143,22 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        todo!()
+        let map: Grid<char> = Self::input().parse().unwrap();
+
+        PipeLoop::find(&map).unwrap().enclosed().to_string()
     }
 
     fn solution(&self) -> &'static str {
         #[cfg(debug_assertions)]
         {
-            "4"
-            // "8"
+            // "4"
+            "8"
+            // "10"
         }
 
         #[cfg(not(debug_assertions))]
         {
-            "6951"
+            "563"
         }
     }
 
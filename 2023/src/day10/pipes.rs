@@ -0,0 +1,312 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::{
+    geom::{Direction, Point},
+    grid::Grid,
+};
+
+/// All pipe tiles. Anything else (ground `.`, start `S`) has no openings.
+pub const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+/// Directions a tile has pipe openings towards.
+pub fn connections(tile: char) -> &'static [Direction] {
+    use Direction::*;
+
+    match tile {
+        '|' => &[North, South],
+        '-' => &[East, West],
+        'L' => &[North, East],
+        'J' => &[North, West],
+        '7' => &[South, West],
+        'F' => &[South, East],
+        _ => &[],
+    }
+}
+
+/// The pipe with openings towards exactly these two directions.
+pub fn pipe(a: Direction, b: Direction) -> Option<char> {
+    PIPES.into_iter().find(|&pipe| {
+        let openings = connections(pipe);
+        openings.contains(&a) && openings.contains(&b)
+    })
+}
+
+/// Where a tile lies relative to the main loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+/// The main loop through the start tile `S`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeLoop {
+    /// The pipe hidden under `S`.
+    pub start_tile: char,
+    /// Every tile of the loop in walking order, starting at `S`.
+    pub tiles: Vec<Point<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoopError {
+    MissingStart,
+    MultipleStarts(Vec<Point<usize>>),
+    /// No two pipes leaving `S` connect back up to each other.
+    NoLoop(Point<usize>),
+    /// More than one loop runs through `S`, each possible as one of these pipes.
+    Ambiguous(Point<usize>, Vec<char>),
+}
+
+impl fmt::Display for LoopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStart => write!(f, "no start tile `S`"),
+            Self::MultipleStarts(starts) => write!(
+                f,
+                "multiple start tiles at {}",
+                starts
+                    .iter()
+                    .map(|p| format!("({}, {})", p.x, p.y))
+                    .join(", ")
+            ),
+            Self::NoLoop(start) => {
+                write!(f, "no loop runs through `S` at ({}, {})", start.x, start.y)
+            }
+            Self::Ambiguous(start, pipes) => write!(
+                f,
+                "`S` at ({}, {}) could be any of {}",
+                start.x,
+                start.y,
+                pipes.iter().join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoopError {}
+
+impl PipeLoop {
+    /// Finds the one loop running through `S`.
+    ///
+    /// Every direction `S` has a connecting neighbour towards is followed. Pipes have
+    /// exactly two openings, so walks either dead-end (leaving the map, or into a pipe
+    /// not connecting back) or find their way back to `S`.
+    pub fn find(map: &Grid<char>) -> Result<Self, LoopError> {
+        let starts = map
+            .indexed_iter()
+            .filter(|&(_, &c)| c == 'S')
+            .map(|(position, _)| Point::from(position))
+            .collect::<Vec<_>>();
+
+        let start = match starts[..] {
+            [] => return Err(LoopError::MissingStart),
+            [start] => start,
+            _ => return Err(LoopError::MultipleStarts(starts)),
+        };
+
+        // A loop leaving one way comes back the other: that way needn't be walked again.
+        let mut returned = Vec::new();
+        let mut loops = Vec::new();
+        for direction in Direction::CARDINAL {
+            if returned.contains(&direction) {
+                continue;
+            }
+
+            if let Some((pipe, back, tiles)) = walk(map, start, direction) {
+                returned.push(back);
+                loops.push((pipe, tiles));
+            }
+        }
+
+        let pipes = loops
+            .iter()
+            .map(|(pipe, _)| *pipe)
+            .unique()
+            .collect::<Vec<_>>();
+
+        match pipes[..] {
+            [] => Err(LoopError::NoLoop(start)),
+            [start_tile] => {
+                let (_, tiles) = loops.into_iter().next().unwrap();
+                Ok(Self { start_tile, tiles })
+            }
+            _ => Err(LoopError::Ambiguous(start, pipes)),
+        }
+    }
+
+    pub fn start(&self) -> Point<usize> {
+        self.tiles[0]
+    }
+
+    /// The tile farthest away from the start, in steps along the loop.
+    pub fn farthest(&self) -> usize {
+        self.tiles.len() / 2
+    }
+
+    /// Tiles enclosed by the loop.
+    ///
+    /// Walking the loop yields its corners, so the shoelace formula gives its area.
+    /// That area is measured between tile *centres*, which is where Pick's theorem
+    /// comes in: `area = inside + boundary / 2 - 1`, with `boundary` being the loop's
+    /// length. No flood filling, no squeezing between pipes, junk pipes are simply
+    /// ignored.
+    pub fn enclosed(&self) -> usize {
+        let twice_area: isize = self
+            .tiles
+            .iter()
+            .circular_tuple_windows()
+            .map(|(a, b)| (a.x * b.y) as isize - (b.x * a.y) as isize)
+            .sum();
+
+        // Walking direction decides the sign.
+        twice_area.unsigned_abs() / 2 + 1 - self.tiles.len() / 2
+    }
+
+    /// Classifies every tile of `map`, which this loop was found in.
+    ///
+    /// Slower than [`Self::enclosed`], but says *which* tiles are enclosed: scanning
+    /// each row, every loop tile with an opening to the north toggles between outside
+    /// and inside. Looking at one side only handles runs like `L--7` (crossing) and
+    /// `L--J` (not crossing) without special cases.
+    pub fn regions(&self, map: &Grid<char>) -> Grid<Region> {
+        let mut regions = Grid::filled(map.width(), map.height(), Region::Outside);
+        for &tile in &self.tiles {
+            regions[tile] = Region::Loop;
+        }
+
+        for i in 0..map.height() {
+            let mut inside = false;
+
+            for j in 0..map.width() {
+                let tile = Point::new(j, i);
+
+                if regions[tile] == Region::Loop {
+                    let pipe = if tile == self.start() {
+                        self.start_tile
+                    } else {
+                        map[tile]
+                    };
+
+                    inside ^= connections(pipe).contains(&Direction::North);
+                } else if inside {
+                    regions[tile] = Region::Inside;
+                }
+            }
+        }
+
+        regions
+    }
+}
+
+/// Follows pipes from `start`, leaving towards `direction`. On returning to `start`,
+/// yields the pipe `start` has to be, the direction it came back from, and all tiles
+/// walked.
+fn walk(
+    map: &Grid<char>,
+    start: Point<usize>,
+    direction: Direction,
+) -> Option<(char, Direction, Vec<Point<usize>>)> {
+    let mut tiles = vec![start];
+    let mut pos = start;
+    let mut heading = direction;
+
+    loop {
+        pos = map.step(pos, heading)?;
+
+        if pos == start {
+            let back = heading.opposite();
+            return Some((pipe(direction, back)?, back, tiles));
+        }
+
+        // Leave by the opening we didn't come in through, if we can come in at all.
+        let openings = connections(map[pos]);
+        let entry = heading.opposite();
+        if !openings.contains(&entry) {
+            return None;
+        }
+
+        heading = *openings.iter().find(|&&d| d != entry).unwrap();
+        tiles.push(pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn find(input: &str) -> Result<PipeLoop, LoopError> {
+        PipeLoop::find(&input.parse().unwrap())
+    }
+
+    #[rstest]
+    #[case(include_str!("input/sample1.txt"), 'F', 4, 1)]
+    #[case(include_str!("input/sample2.txt"), 'F', 8, 1)]
+    #[case(include_str!("input/sample3.txt"), 'F', 23, 4)]
+    #[case(include_str!("input/sample4.txt"), 'F', 22, 4)]
+    #[case(include_str!("input/sample5.txt"), 'F', 70, 8)]
+    #[case(include_str!("input/sample6.txt"), '7', 80, 10)]
+    fn test_samples(
+        #[case] input: &str,
+        #[case] start_tile: char,
+        #[case] farthest: usize,
+        #[case] enclosed: usize,
+    ) {
+        let map = input.parse().unwrap();
+        let pipe_loop = PipeLoop::find(&map).unwrap();
+
+        assert_eq!(pipe_loop.start_tile, start_tile);
+        assert_eq!(pipe_loop.farthest(), farthest);
+        assert_eq!(pipe_loop.enclosed(), enclosed);
+
+        let regions = pipe_loop.regions(&map);
+        assert_eq!(
+            regions.iter().filter(|&&r| r == Region::Inside).count(),
+            enclosed
+        );
+        assert_eq!(
+            regions.iter().filter(|&&r| r == Region::Loop).count(),
+            farthest * 2
+        );
+    }
+
+    #[test]
+    fn test_order() {
+        let pipe_loop = find(".....\n.S-7.\n.|.|.\n.L-J.\n.....").unwrap();
+
+        // Both directions are fine, but consecutive tiles must be adjacent.
+        assert_eq!(pipe_loop.start(), Point::new(1, 1));
+        assert_eq!(pipe_loop.tiles.len(), 8);
+        for (a, b) in pipe_loop.tiles.iter().circular_tuple_windows() {
+            assert_eq!(a.manhattan(b), 1);
+        }
+    }
+
+    #[rstest]
+    #[case("...\n...", LoopError::MissingStart)]
+    #[case(
+        "S.\n.S",
+        LoopError::MultipleStarts(vec![Point::new(0, 0), Point::new(1, 1)])
+    )]
+    #[case("-S-\n...", LoopError::NoLoop(Point::new(1, 0)))]
+    #[case("F7.\nS-.", LoopError::NoLoop(Point::new(0, 1)))]
+    #[case(
+        "F-7..\n|.|..\nL-S-7\n..|.|\n..L-J",
+        LoopError::Ambiguous(Point::new(2, 2), vec!['J', 'F'])
+    )]
+    fn test_errors(#[case] input: &str, #[case] expected: LoopError) {
+        assert_eq!(find(input), Err(expected));
+    }
+
+    #[test]
+    fn test_branch_resolved() {
+        // Three neighbours connect to `S`, but only two of them close a loop.
+        let pipe_loop = find("F7.\n|S-\nLJ.").unwrap();
+
+        assert_eq!(pipe_loop.start_tile, '|');
+        assert_eq!(pipe_loop.tiles.len(), 6);
+    }
+}
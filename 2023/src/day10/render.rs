@@ -0,0 +1,180 @@
+use std::io::{self, IsTerminal, Write};
+
+use super::pipes::{PipeLoop, Region};
+use crate::{geom::Point, grid::Grid};
+
+/// How to draw a pipe map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// ANSI colours: the loop in bold, junk pipes dimmed, enclosed tiles green and
+    /// outside ones blue.
+    Color,
+    /// No escape codes, for files and pipes. The loop is drawn, everything else is
+    /// marked `I` (enclosed) or `O` (outside), as in the puzzle description.
+    Plain,
+}
+
+impl Style {
+    /// Colours for terminals, unless `NO_COLOR` is set.
+    pub fn detect(stream: &impl IsTerminal) -> Self {
+        if stream.is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+            Self::Color
+        } else {
+            Self::Plain
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const LOOP: &str = "\x1b[1;33m";
+const INSIDE: &str = "\x1b[32m";
+const INSIDE_JUNK: &str = "\x1b[2;32m";
+const OUTSIDE: &str = "\x1b[34m";
+const OUTSIDE_JUNK: &str = "\x1b[2;34m";
+
+/// The box-drawing version of a pipe. Ground (and anything else) is left alone.
+pub fn box_drawing(tile: char) -> char {
+    match tile {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        _ => tile,
+    }
+}
+
+/// Draws `map`, with `pipe_loop` being its main loop. Rows end in newlines.
+pub fn render(map: &Grid<char>, pipe_loop: &PipeLoop, style: Style) -> String {
+    let regions = pipe_loop.regions(map);
+    let mut out = String::with_capacity(map.width() * map.height() * 4);
+
+    for i in 0..map.height() {
+        // Only emit escape codes when they change, which is rarely along a row.
+        let mut current = None;
+
+        for j in 0..map.width() {
+            let tile = Point::new(j, i);
+            let pipe = if tile == pipe_loop.start() {
+                pipe_loop.start_tile
+            } else {
+                map[tile]
+            };
+
+            let (code, c) = match (style, regions[tile]) {
+                (Style::Plain, Region::Loop) => (None, box_drawing(pipe)),
+                (Style::Plain, Region::Inside) => (None, 'I'),
+                (Style::Plain, Region::Outside) => (None, 'O'),
+                (Style::Color, region) => {
+                    let junk = pipe != '.';
+                    let code = match region {
+                        Region::Loop => LOOP,
+                        Region::Inside if junk => INSIDE_JUNK,
+                        Region::Inside => INSIDE,
+                        Region::Outside if junk => OUTSIDE_JUNK,
+                        Region::Outside => OUTSIDE,
+                    };
+
+                    (Some(code), if junk { box_drawing(pipe) } else { '·' })
+                }
+            };
+
+            if let Some(code) = code.filter(|&code| Some(code) != current) {
+                out.push_str(RESET);
+                out.push_str(code);
+                current = Some(code);
+            }
+
+            out.push(c);
+        }
+
+        if current.is_some() {
+            out.push_str(RESET);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Draws to standard output, in colour if that's a terminal.
+pub fn print(map: &Grid<char>, pipe_loop: &PipeLoop) -> io::Result<()> {
+    let stdout = io::stdout();
+    let style = Style::detect(&stdout);
+
+    stdout
+        .lock()
+        .write_all(render(map, pipe_loop, style).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(input: &str) -> (Grid<char>, PipeLoop) {
+        let map = input.parse().unwrap();
+        let pipe_loop = PipeLoop::find(&map).unwrap();
+
+        (map, pipe_loop)
+    }
+
+    #[test]
+    fn test_plain() {
+        let (map, pipe_loop) = sample(include_str!("input/sample4.txt"));
+
+        assert_eq!(
+            render(&map, &pipe_loop, Style::Plain),
+            "\
+OOOOOOOOOO
+O┌──────┐O
+O│┌────┐│O
+O││OOOO││O
+O││OOOO││O
+O│└─┐┌─┘│O
+O│II││II│O
+O└──┘└──┘O
+OOOOOOOOOO
+"
+        );
+    }
+
+    #[test]
+    fn test_plain_junk() {
+        // Junk pipes are just tiles, inside or out.
+        let (map, pipe_loop) = sample(include_str!("input/sample1.txt"));
+
+        assert_eq!(
+            render(&map, &pipe_loop, Style::Plain),
+            "\
+OOOOO
+O┌─┐O
+O│I│O
+O└─┘O
+OOOOO
+"
+        );
+    }
+
+    #[test]
+    fn test_color() {
+        let (map, pipe_loop) = sample("-F7\n.SJ");
+        let rendered = render(&map, &pipe_loop, Style::Color);
+
+        assert_eq!(
+            rendered,
+            format!(
+                "{RESET}{OUTSIDE_JUNK}─{RESET}{LOOP}┌┐{RESET}\n\
+                 {RESET}{OUTSIDE}·{RESET}{LOOP}└┘{RESET}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_box_drawing() {
+        assert_eq!(
+            "|-LJ7F.S".chars().map(box_drawing).collect::<String>(),
+            "│─└┘┐┌.S"
+        );
+    }
+}
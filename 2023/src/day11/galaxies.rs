@@ -0,0 +1,126 @@
+use crate::{geom::Point, sparse::SparseGrid};
+
+/// Sum of distances between all pairs of galaxies in `image`, after every empty row
+/// and column has grown into `factor` of them. A `factor` of 1 leaves the image as is,
+/// 0 collapses empty space entirely.
+///
+/// `None` if expanded coordinates overflow `u64`. The sum itself can't overflow: that'd
+/// take billions of galaxies.
+pub fn expanded_distances(image: &SparseGrid, factor: u64) -> Option<u128> {
+    let galaxies = image.expand(factor)?;
+
+    // Summing over all pairs directly is quadratic, which is fine for a few hundred
+    // galaxies, but not beyond.
+    Some(pairwise_distances(&galaxies))
+}
+
+/// Sum of Manhattan distances over all pairs of points, in `O(n log n)`.
+///
+/// Manhattan distance splits into independent axes. Along one axis, once sorted, the
+/// `k`-th value is the larger one in exactly `k` pairs, contributing
+/// `k * value - (sum of all values before it)`. A running prefix sum makes that a
+/// single pass. `u128`, since for 10⁵ galaxies spread out by a million-fold expansion,
+/// the total gets close to `u64::MAX`.
+pub fn pairwise_distances(points: &[Point<u64>]) -> u128 {
+    let mut xs = points.iter().map(|p| p.x).collect::<Vec<_>>();
+    let mut ys = points.iter().map(|p| p.y).collect::<Vec<_>>();
+
+    axis_distances(&mut xs) + axis_distances(&mut ys)
+}
+
+fn axis_distances(values: &mut [u64]) -> u128 {
+    // Usually sorted already for rows, scanned top to bottom; that's cheap to sort.
+    values.sort_unstable();
+
+    let mut prefix = 0;
+    let mut total = 0;
+
+    for (k, &value) in values.iter().enumerate() {
+        total += k as u128 * value as u128 - prefix;
+        prefix += value as u128;
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = include_str!("input/sample1.txt");
+
+    #[rstest]
+    #[case(0, 210)]
+    #[case(1, 292)]
+    #[case(2, 374)]
+    #[case(10, 1030)]
+    #[case(100, 8410)]
+    fn test_expanded_distances(#[case] factor: u64, #[case] expected: u128) {
+        let image = SparseGrid::parse(SAMPLE, b'#');
+
+        assert_eq!(expanded_distances(&image, factor), Some(expected));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let image = SparseGrid::parse(SAMPLE, b'#');
+
+        assert_eq!(expanded_distances(&image, u64::MAX), None);
+        assert!(expanded_distances(&image, u64::MAX / 8).is_some());
+    }
+
+    fn brute_force(points: &[Point<u64>]) -> u128 {
+        let mut n = 0;
+
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                n += a.manhattan(b) as u128;
+            }
+        }
+
+        n
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        assert_eq!(pairwise_distances(&[]), 0);
+        assert_eq!(pairwise_distances(&[Point::new(3, 4)]), 0);
+        assert_eq!(
+            pairwise_distances(&[Point::new(1, 6), Point::new(5, 11)]),
+            9
+        );
+    }
+
+    #[test]
+    fn test_many() {
+        // Way too many for the quadratic approach. All in one column, `d` rows apart in
+        // `n - d` pairs, which sums up to `(n - 1) * n * (n + 1) / 6`.
+        let points = (0..200_000)
+            .rev()
+            .map(|i| Point::new(0, i * 1_000_000))
+            .collect::<Vec<_>>();
+
+        let n = points.len() as u128;
+
+        assert_eq!(
+            pairwise_distances(&points),
+            1_000_000 * (n - 1) * n * (n + 1) / 6
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(
+            points in prop::collection::vec((0..1000_u64, 0..1000_u64), 0..50)
+        ) {
+            let points = points
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y))
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(pairwise_distances(&points), brute_force(&points));
+        }
+    }
+}
@@ -1,6 +1,5 @@
-use smallvec::SmallVec;
-
-use crate::Challenge;
+use super::galaxies::expanded_distances;
+use crate::{sparse::SparseGrid, Challenge};
 
 /// --- Day 11: Cosmic Expansion ---
 ///
@@ -144,59 +143,10 @@ impl Challenge for Part {
     }
 
     fn solve(&self) -> String {
-        let input = Self::input();
-
-        // `SmallVec` is approx. 10% faster (110µs -> ~95µs)
-        let mut coords = SmallVec::<[(usize, usize); 1024]>::new();
-
-        let mut empty_cols = Vec::from_iter(0..input.lines().next().unwrap().len());
-
-        let mut row_offset = 0;
-        for (i, line) in input.lines().enumerate() {
-            let mut empty = true;
-
-            for (j, c) in line.chars().enumerate() {
-                if c == '#' {
-                    coords.push((i + row_offset, j));
-
-                    empty_cols.retain(|&x| x != j);
-                    empty = false;
-                }
-            }
-
-            row_offset += empty as usize;
-        }
-
-        // Expand. Cannot do this until now, until empty cols aren't known until now.
-        for empty_col in empty_cols.iter().rev() {
-            coords.iter_mut().for_each(|(_, j)| {
-                if *j > *empty_col {
-                    *j += 1;
-                }
-            });
-        }
-
-        let mut n = 0;
-
-        // `combinations_with_replacement(2)` is massively slower (factor 10), probably
-        // because it clones.
-        (0..coords.len()).for_each(|left_index| {
-            (left_index + 1..coords.len()).for_each(|right_index| {
-                let left = coords[left_index];
-                let right = coords[right_index];
-
-                let max_i = left.0.max(right.0);
-                let min_i = left.0.min(right.0);
-                let max_j = left.1.max(right.1);
-                let min_j = left.1.min(right.1);
-
-                let distance = max_i - min_i + max_j - min_j;
-
-                n += distance;
-            })
-        });
+        let image = SparseGrid::parse(Self::input(), b'#');
 
-        n.to_string()
+        // Twice as big, that is.
+        expanded_distances(&image, 2).unwrap().to_string()
     }
 
     fn solution(&self) -> &'static str {
@@ -0,0 +1,329 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// A point on the plane.
+///
+/// Follows screen (and puzzle input) conventions: `x` grows to the east, `y` grows to
+/// the *south*. That way, a `Point<usize>` maps onto a [`Grid`](crate::grid::Grid)
+/// position as `(row, column) == (y, x)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A displacement between two [`Point`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vector<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// Compass directions, in clockwise order starting north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Taxicab distance: the number of orthogonal steps between two points.
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Chessboard distance: the number of king moves between two points.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl Point<usize> {
+    /// Moves by `vector`, unless that leaves the non-negative quadrant.
+    pub fn checked_add(&self, vector: Vector<isize>) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(vector.x)?,
+            y: self.y.checked_add_signed(vector.y)?,
+        })
+    }
+
+    /// Takes a single step towards `direction`, unless that leaves the non-negative
+    /// quadrant.
+    pub fn checked_step(&self, direction: Direction) -> Option<Self> {
+        self.checked_add(direction.vector())
+    }
+}
+
+impl<T> Vector<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Direction {
+    /// North, east, south, west.
+    pub const CARDINAL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// All eight directions, clockwise starting north.
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Unit step into this direction. Diagonals step along both axes.
+    pub const fn vector(self) -> Vector<isize> {
+        match self {
+            Self::North => Vector::new(0, -1),
+            Self::NorthEast => Vector::new(1, -1),
+            Self::East => Vector::new(1, 0),
+            Self::SouthEast => Vector::new(1, 1),
+            Self::South => Vector::new(0, 1),
+            Self::SouthWest => Vector::new(-1, 1),
+            Self::West => Vector::new(-1, 0),
+            Self::NorthWest => Vector::new(-1, -1),
+        }
+    }
+
+    /// Turns by 90° clockwise.
+    pub const fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns by 90° counterclockwise.
+    pub const fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub const fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as u8 % 2 == 1
+    }
+
+    /// Turns clockwise by `eighths` of a full circle.
+    const fn rotate(self, eighths: u8) -> Self {
+        Self::ALL[(self as usize + eighths as usize) % Self::ALL.len()]
+    }
+}
+
+impl<T: Add<Output = T>> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Vector<T>) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign<Vector<T>> for Point<T> {
+    fn add_assign(&mut self, rhs: Vector<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector<T>) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vector<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// Grid positions are `(row, column)`, see [`Point`].
+impl From<(usize, usize)> for Point<usize> {
+    fn from((row, column): (usize, usize)) -> Self {
+        Self::new(column, row)
+    }
+}
+
+impl From<Point<usize>> for (usize, usize) {
+    fn from(point: Point<usize>) -> Self {
+        (point.y, point.x)
+    }
+}
+
+/// Works for unsigned types, where `(a - b).abs()` would underflow.
+#[inline]
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+
+        assert_eq!(b - a, Vector::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(b - (b - a), a);
+        assert_eq!(-(b - a) * 2, Vector::new(-6, 8));
+
+        let mut c = a;
+        c += Vector::new(1, 1);
+        assert_eq!(c, Point::new(2, 3));
+    }
+
+    #[rstest]
+    #[case(Point::new(0, 0), Point::new(0, 0), 0, 0)]
+    #[case(Point::new(1, 6), Point::new(5, 11), 9, 5)]
+    #[case(Point::new(5, 11), Point::new(1, 6), 9, 5)]
+    #[case(Point::new(3, 0), Point::new(0, 3), 6, 3)]
+    fn test_distances(
+        #[case] a: Point<usize>,
+        #[case] b: Point<usize>,
+        #[case] manhattan: usize,
+        #[case] chebyshev: usize,
+    ) {
+        assert_eq!(a.manhattan(&b), manhattan);
+        assert_eq!(a.chebyshev(&b), chebyshev);
+    }
+
+    #[test]
+    fn test_distances_signed() {
+        let a = Point::new(-3, 4);
+        let b = Point::new(2, -1);
+
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 5);
+    }
+
+    #[rstest]
+    #[case(Direction::North, Direction::East, Direction::West, Direction::South)]
+    #[case(Direction::East, Direction::South, Direction::North, Direction::West)]
+    #[case(
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::NorthWest,
+        Direction::SouthWest
+    )]
+    #[case(
+        Direction::NorthWest,
+        Direction::NorthEast,
+        Direction::SouthWest,
+        Direction::SouthEast
+    )]
+    fn test_turning(
+        #[case] direction: Direction,
+        #[case] right: Direction,
+        #[case] left: Direction,
+        #[case] opposite: Direction,
+    ) {
+        assert_eq!(direction.turn_right(), right);
+        assert_eq!(direction.turn_left(), left);
+        assert_eq!(direction.opposite(), opposite);
+        assert_eq!(direction.vector(), -opposite.vector());
+    }
+
+    #[test]
+    fn test_full_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction
+                    .turn_right()
+                    .turn_right()
+                    .turn_right()
+                    .turn_right(),
+                direction
+            );
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+
+    #[test]
+    fn test_diagonal() {
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+        assert!(Direction::CARDINAL.iter().all(|d| !d.is_diagonal()));
+    }
+
+    #[test]
+    fn test_checked_step() {
+        let origin = Point::new(0_usize, 0);
+
+        assert_eq!(origin.checked_step(Direction::North), None);
+        assert_eq!(origin.checked_step(Direction::West), None);
+        assert_eq!(origin.checked_step(Direction::SouthWest), None);
+        assert_eq!(
+            origin.checked_step(Direction::SouthEast),
+            Some(Point::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn test_grid_position_roundtrip() {
+        let point = Point::from((2, 5));
+
+        assert_eq!(point, Point::new(5, 2));
+        assert_eq!(<(usize, usize)>::from(point), (2, 5));
+    }
+}
@@ -0,0 +1,446 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::geom::{Direction, Point};
+
+/// Position in a [`Grid`], as `(row, column)`.
+pub type Position = (usize, usize);
+
+/// A rectangular, two-dimensional grid, as found in so many puzzle inputs.
+///
+/// Cells are stored contiguously in row-major order, so walking rows is cheap and
+/// there's a single allocation, unlike with `Vec<Vec<T>>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// Row has a different length than the first one.
+    Ragged {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// Character could not be converted into a cell.
+    InvalidCell { position: Position, char: char },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ragged {
+                row,
+                expected,
+                actual,
+            } => write!(f, "row {row} has length {actual}, expected {expected}"),
+            Self::InvalidCell { position, char } => {
+                write!(f, "invalid cell {char:?} at {position:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells doesn't match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "cells don't fit dimensions");
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .map(&mut f)
+            .collect();
+
+        Self::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (i, j): Position) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.offset(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let offset = self.offset(position);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `position` by `(di, dj)`, as long as it stays inside the grid.
+    pub fn offset_by(&self, (i, j): Position, (di, dj): (isize, isize)) -> Option<Position> {
+        let position = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Takes a single step towards `direction`, as long as it stays inside the grid.
+    pub fn step(&self, point: Point<usize>, direction: Direction) -> Option<Point<usize>> {
+        point
+            .checked_step(direction)
+            .filter(|&next| self.contains(next.into()))
+    }
+
+    /// Positions of the (up to) four orthogonal neighbors, clockwise starting north.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbors(position, &Direction::CARDINAL)
+    }
+
+    /// Positions of the (up to) eight neighbors including diagonals, clockwise starting
+    /// north.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbors(position, &Direction::ALL)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        position: Position,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Position> + 'a {
+        directions
+            .iter()
+            .filter_map(move |&direction| self.step(position.into(), direction))
+            .map(Position::from)
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // `chunks_exact` would panic for zero-width grids.
+        (0..self.height).map(|i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(j < self.width, "column {j} out of bounds");
+
+        self.cells.iter().skip(j).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|j| self.column(j))
+    }
+
+    /// All cells in row-major order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.cells.iter()
+    }
+
+    /// All cells in row-major order, alongside their positions.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(offset, cell)| ((offset / width, offset % width), cell))
+    }
+
+    /// Position of the first cell (in row-major order) matching `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|offset| (offset / self.width, offset % self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    #[inline]
+    fn offset(&self, (i, j): Position) -> usize {
+        i * self.width + j
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Mirrors along the main diagonal: rows become columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(i, j)| self[(j, i)].clone())
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(i, j)| {
+            self[(self.height - 1 - j, i)].clone()
+        })
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |(i, j)| {
+            self[(j, self.width - 1 - i)].clone()
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        assert!(self.contains(position), "{position:?} out of bounds");
+        &self.cells[self.offset(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        assert!(self.contains(position), "{position:?} out of bounds");
+        let offset = self.offset(position);
+        &mut self.cells[offset]
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        &self[Position::from(point)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        &mut self[Position::from(point)]
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    /// Parses one row per line, one cell per character.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (i, line) in s.lines().enumerate() {
+            let mut n = 0;
+
+            for (j, char) in line.chars().enumerate() {
+                let cell = T::try_from(char).map_err(|_| ParseGridError::InvalidCell {
+                    position: (i, j),
+                    char,
+                })?;
+
+                cells.push(cell);
+                n += 1;
+            }
+
+            match width {
+                None => width = Some(n),
+                Some(expected) if expected != n => {
+                    return Err(ParseGridError::Ragged {
+                        row: i,
+                        expected,
+                        actual: n,
+                    })
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        Ok(Self::new(width.unwrap_or_default(), height, cells))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const SAMPLE: &str = "abc\ndef";
+
+    fn sample() -> Grid<char> {
+        SAMPLE.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[(1, 2)], 'f');
+    }
+
+    #[test]
+    fn test_parse_ragged() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_cell() {
+        #[derive(Debug)]
+        struct Digit(u32);
+
+        impl TryFrom<char> for Digit {
+            type Error = ();
+
+            fn try_from(value: char) -> Result<Self, Self::Error> {
+                value.to_digit(10).map(Self).ok_or(())
+            }
+        }
+
+        let grid: Grid<Digit> = "12\n34".parse().unwrap();
+        assert_eq!(grid[(1, 0)].0, 3);
+
+        assert_eq!(
+            "12\n3x".parse::<Grid<Digit>>().unwrap_err(),
+            ParseGridError::InvalidCell {
+                position: (1, 1),
+                char: 'x'
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() {
+        let grid = sample();
+
+        assert_eq!(grid.get((1, 1)), Some(&'e'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+    }
+
+    #[rstest]
+    #[case((0, 0), vec![(0, 1), (1, 0)])]
+    #[case((1, 1), vec![(0, 1), (1, 2), (1, 0)])]
+    #[case((0, 2), vec![(1, 2), (0, 1)])]
+    fn test_neighbors4(#[case] position: Position, #[case] expected: Vec<Position>) {
+        assert_eq!(sample().neighbors4(position).collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    #[case((0, 0), vec![(0, 1), (1, 1), (1, 0)])]
+    #[case((1, 1), vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)])]
+    fn test_neighbors8(#[case] position: Position, #[case] expected: Vec<Position>) {
+        assert_eq!(sample().neighbors8(position).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_step() {
+        let grid = sample();
+        let corner = Point::new(2, 1);
+
+        assert_eq!(grid[corner], 'f');
+        assert_eq!(grid.step(corner, Direction::East), None);
+        assert_eq!(grid.step(corner, Direction::South), None);
+        assert_eq!(
+            grid.step(corner, Direction::NorthWest),
+            Some(Point::new(1, 0))
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.copied().collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_position() {
+        let grid = sample();
+
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.position(|&c| c == 'z'), None);
+    }
+
+    #[rstest]
+    #[case(Grid::transpose, "ad\nbe\ncf")]
+    #[case(Grid::rotate_clockwise, "da\neb\nfc")]
+    #[case(Grid::rotate_counterclockwise, "cf\nbe\nad")]
+    fn test_reshape(#[case] f: fn(&Grid<char>) -> Grid<char>, #[case] expected: &str) {
+        assert_eq!(f(&sample()).to_string(), expected);
+    }
+
+    #[test]
+    fn test_rotate_full_circle() {
+        let grid = sample();
+
+        let mut rotated = grid.clone();
+        for _ in 0..4 {
+            rotated = rotated.rotate_clockwise();
+        }
+
+        assert_eq!(rotated, grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        assert_eq!(sample().to_string(), SAMPLE);
+    }
+}
@@ -0,0 +1,700 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Range, Sub},
+};
+
+use num::{Bounded, CheckedAdd, CheckedSub, Zero};
+
+/// Whether two half-open ranges share at least one element.
+#[inline]
+pub fn intersects<T: Ord>(a: &Range<T>, b: &Range<T>) -> bool {
+    a.start < b.end && b.start < a.end && !a.is_empty() && !b.is_empty()
+}
+
+/// A set of values, stored as half-open intervals.
+///
+/// The representation is kept *normalized*: intervals are non-empty, sorted, and
+/// neither overlap nor touch (touching ones are merged). Hence, two sets are equal
+/// exactly if their representations are, and all operations can work as linear sweeps
+/// over both operands.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The normalized intervals, sorted ascending.
+    pub fn intervals(&self) -> &[Range<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|range| range.start)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self.intervals.partition_point(|range| range.end <= *value);
+
+        self.intervals
+            .get(i)
+            .is_some_and(|range| range.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut intervals = Vec::with_capacity(self.intervals.len() + other.intervals.len());
+
+        let mut left = self.intervals.iter().peekable();
+        let mut right = other.intervals.iter().peekable();
+
+        // Merge step of merge sort; results are sorted by start, so only need to
+        // coalesce neighbors.
+        loop {
+            let next = match (left.peek(), right.peek()) {
+                (Some(l), Some(r)) if l.start <= r.start => left.next(),
+                (Some(_), Some(_)) => right.next(),
+                (Some(_), None) => left.next(),
+                (None, Some(_)) => right.next(),
+                (None, None) => break,
+            };
+
+            push_coalescing(&mut intervals, next.unwrap().clone());
+        }
+
+        Self { intervals }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+
+        let (mut i, mut j) = (0, 0);
+        while let (Some(l), Some(r)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let start = l.start.max(r.start);
+            let end = l.end.min(r.end);
+
+            if start < end {
+                intervals.push(start..end);
+            }
+
+            // Whichever ends first cannot overlap anything further on the other side.
+            match l.end.cmp(&r.end) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut cutters = other.intervals.iter().peekable();
+
+        for range in &self.intervals {
+            let mut start = range.start;
+
+            // Skip over cutters fully left of this range; they're also left of all
+            // later ones.
+            while cutters.next_if(|cutter| cutter.end <= start).is_some() {}
+
+            let mut remaining = cutters.clone();
+            while let Some(cutter) = remaining.next_if(|cutter| cutter.start < range.end) {
+                if start < cutter.start {
+                    intervals.push(start..cutter.start);
+                }
+
+                start = start.max(cutter.end);
+            }
+
+            if start < range.end {
+                intervals.push(start..range.end);
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Whether any value of `range` is in the set.
+    pub fn intersects(&self, range: &Range<T>) -> bool {
+        let i = self.intervals.partition_point(|r| r.end <= range.start);
+
+        self.intervals.get(i).is_some_and(|r| intersects(r, range))
+    }
+}
+
+impl<T: Copy + Ord + Bounded> IntervalSet<T> {
+    /// All values *not* in the set.
+    ///
+    /// Intervals are half-open, so `T::max_value()` itself can never be a member:
+    /// neither of a set nor of its complement.
+    pub fn complement(&self) -> Self {
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        let mut start = T::min_value();
+
+        for range in &self.intervals {
+            if start < range.start {
+                intervals.push(start..range.start);
+            }
+
+            start = range.end;
+        }
+
+        if start < T::max_value() {
+            intervals.push(start..T::max_value());
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<_> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut intervals = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            push_coalescing(&mut intervals, range);
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::from_iter([range])
+    }
+}
+
+/// Pushes `range`, which starts no earlier than the last interval, merging the two if
+/// they overlap or touch.
+#[inline]
+fn push_coalescing<T: Copy + Ord>(intervals: &mut Vec<Range<T>>, range: Range<T>) {
+    if range.is_empty() {
+        return;
+    }
+
+    match intervals.last_mut() {
+        Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+        _ => intervals.push(range),
+    }
+}
+
+/// Moves all values in `range` by `by`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shift<T> {
+    pub range: Range<T>,
+    pub by: T,
+}
+
+/// A function shifting disjoint intervals of values by individual amounts, leaving all
+/// other values untouched. That's what one of day 5's almanac maps is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PiecewiseShift<T> {
+    /// Sorted by start, disjoint.
+    shifts: Vec<Shift<T>>,
+}
+
+impl<T> PiecewiseShift<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// # Panics
+    ///
+    /// If shifts overlap, as their mapping would be ambiguous.
+    pub fn new(shifts: impl IntoIterator<Item = Shift<T>>) -> Self {
+        let mut shifts: Vec<_> = shifts.into_iter().filter(|s| !s.range.is_empty()).collect();
+        shifts.sort_unstable_by_key(|s| s.range.start);
+
+        assert!(
+            shifts
+                .windows(2)
+                .all(|pair| pair[0].range.end <= pair[1].range.start),
+            "overlapping shifts are ambiguous"
+        );
+
+        Self { shifts }
+    }
+
+    /// The shifts, sorted by start.
+    pub fn shifts(&self) -> &[Shift<T>] {
+        &self.shifts
+    }
+
+    /// Maps a single value, in logarithmic time.
+    pub fn get(&self, value: T) -> T {
+        let i = self.shifts.partition_point(|s| s.range.end <= value);
+
+        match self.shifts.get(i) {
+            Some(shift) if shift.range.contains(&value) => value + shift.by,
+            _ => value,
+        }
+    }
+
+    /// Maps all values of a set.
+    ///
+    /// Splits intervals at shift boundaries, so yields at most `n + 2m` pieces. Finding
+    /// the first shift relevant to an interval is a binary search, so querying a single
+    /// range is `O(log m)` plus its number of pieces. The pieces then need
+    /// re-normalizing, as shifting breaks their order. In total, that's
+    /// `O((n + m) log(n + m))`.
+    pub fn apply(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut pieces = Vec::with_capacity(set.intervals.len() + 2 * self.shifts.len());
+
+        for range in &set.intervals {
+            self.for_each_piece(range, |piece, by| match by {
+                Some(by) => pieces.push(piece.start + by..piece.end + by),
+                None => pieces.push(piece),
+            });
+        }
+
+        IntervalSet::from_iter(pieces)
+    }
+
+    /// Splits `range` at shift boundaries, calling `f` with each piece (ascending) and
+    /// the amount it's shifted by, if any.
+    fn for_each_piece(&self, range: &Range<T>, mut f: impl FnMut(Range<T>, Option<T>)) {
+        let mut k = self.shifts.partition_point(|s| s.range.end <= range.start);
+        let mut cursor = range.start;
+
+        while cursor < range.end {
+            match self.shifts.get(k) {
+                Some(shift) if shift.range.start < range.end => {
+                    if cursor < shift.range.start {
+                        f(cursor..shift.range.start, None);
+                        cursor = shift.range.start;
+                    }
+
+                    let end = range.end.min(shift.range.end);
+                    f(cursor..end, Some(shift.by));
+                    cursor = end;
+                    k += 1;
+                }
+                _ => {
+                    f(cursor..range.end, None);
+                    cursor = range.end;
+                }
+            }
+        }
+    }
+}
+
+impl<T> PiecewiseShift<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Bounded + Zero + CheckedAdd + CheckedSub,
+{
+    /// Composes `self` and `next` into a single map, equivalent to applying `self`,
+    /// then `next`.
+    ///
+    /// The result is again a flat list of shifts, so lookups stay logarithmic no
+    /// matter how many maps were chained. `None` if shifted values, or how far the
+    /// composed map shifts them, don't fit `T`.
+    pub fn then(&self, next: &Self) -> Option<Self> {
+        let mut shifts: Vec<Shift<T>> = Vec::with_capacity(self.shifts.len() + next.shifts.len());
+        let mut fits = true;
+
+        // Walk the *entire* domain, as unshifted values might well be shifted by `next`.
+        self.for_each_piece(&(T::min_value()..T::max_value()), |range, by| {
+            let by = by.unwrap_or_else(T::zero);
+            let Some(image) = shifted(&range, by) else {
+                fits = false;
+                return;
+            };
+
+            next.for_each_piece(&image, |piece, next_by| {
+                let Some(total) = by.checked_add(&next_by.unwrap_or_else(T::zero)) else {
+                    fits = false;
+                    return;
+                };
+
+                if total.is_zero() {
+                    return;
+                }
+
+                // Pieces arrive sorted by source, so only need to look back once to
+                // coalesce. They're part of the image, so shifting back fits.
+                let source = piece.start - by..piece.end - by;
+                match shifts.last_mut() {
+                    Some(last) if last.range.end == source.start && last.by == total => {
+                        last.range.end = source.end;
+                    }
+                    _ => shifts.push(Shift {
+                        range: source,
+                        by: total,
+                    }),
+                }
+            });
+        });
+
+        fits.then_some(Self { shifts })
+    }
+
+    /// The map going the opposite way, if `self` is a bijection (no two values map to
+    /// the same one) and its images fit `T`.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut images = Vec::with_capacity(2 * self.shifts.len() + 1);
+        let mut fits = true;
+
+        self.for_each_piece(
+            &(T::min_value()..T::max_value()),
+            |range, by| match shifted(&range, by.unwrap_or_else(T::zero)) {
+                Some(image) => images.push(image),
+                None => fits = false,
+            },
+        );
+
+        images.sort_unstable_by_key(|r| r.start);
+        if !fits || !images.windows(2).all(|pair| pair[0].end <= pair[1].start) {
+            return None;
+        }
+
+        let shifts = self
+            .shifts
+            .iter()
+            .map(|s| {
+                Some(Shift {
+                    range: shifted(&s.range, s.by)?,
+                    by: T::zero().checked_sub(&s.by)?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self::new(shifts))
+    }
+}
+
+/// `range` moved by `by`, if both its ends still fit.
+fn shifted<T: CheckedAdd>(range: &Range<T>, by: T) -> Option<Range<T>> {
+    Some(range.start.checked_add(&by)?..range.end.checked_add(&by)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    /// Sets are modelled as plain sets of values. `i8` keeps the domain small enough to
+    /// enumerate.
+    type Model = BTreeSet<i8>;
+
+    fn model(set: &IntervalSet<i8>) -> Model {
+        set.intervals().iter().flat_map(|r| r.clone()).collect()
+    }
+
+    fn is_normalized(set: &IntervalSet<i8>) -> bool {
+        set.intervals().iter().all(|r| !r.is_empty())
+            && set.intervals().windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    fn range() -> impl Strategy<Value = Range<i8>> {
+        (any::<i8>(), 0..48_i8).prop_map(|(start, length)| start..start.saturating_add(length))
+    }
+
+    fn set() -> impl Strategy<Value = IntervalSet<i8>> {
+        prop::collection::vec(range(), 0..8).prop_map(IntervalSet::from_iter)
+    }
+
+    /// Shifts confined to a sub-domain, so that shifted values cannot overflow.
+    fn piecewise_shift() -> impl Strategy<Value = PiecewiseShift<i8>> {
+        prop::collection::vec((-60..60_i8, 0..20_i8, -40..40_i8), 0..6).prop_map(|shifts| {
+            let mut taken = BTreeSet::new();
+
+            PiecewiseShift::new(shifts.into_iter().filter_map(|(start, length, by)| {
+                let range = start..start + length;
+
+                // Drop overlapping ones, they are rejected.
+                range
+                    .clone()
+                    .all(|v| taken.insert(v))
+                    .then_some(Shift { range, by })
+            }))
+        })
+    }
+
+    #[rstest]
+    #[case(0..5, 3..8, true)]
+    #[case(0..5, 5..8, false)]
+    #[case(5..8, 0..5, false)]
+    #[case(0..10, 3..4, true)]
+    #[case(3..3, 0..10, false)]
+    fn test_intersects(#[case] a: Range<i32>, #[case] b: Range<i32>, #[case] expected: bool) {
+        assert_eq!(intersects(&a, &b), expected);
+        assert_eq!(intersects(&b, &a), expected);
+    }
+
+    #[test]
+    fn test_normalization() {
+        let set = IntervalSet::from_iter([5..7, 0..2, 1..3, 3..4, 9..9, 6..8]);
+
+        assert_eq!(set.intervals(), [0..4, 5..8]);
+    }
+
+    #[test]
+    fn test_complement_edges() {
+        assert_eq!(
+            IntervalSet::<u8>::new().complement(),
+            IntervalSet::from(0..u8::MAX)
+        );
+        assert_eq!(
+            IntervalSet::from(0..u8::MAX).complement(),
+            IntervalSet::new()
+        );
+    }
+
+    #[test]
+    fn test_apply_day05_sample() {
+        // `seed-to-soil map:` from the day 5 sample.
+        let map = PiecewiseShift::new([
+            Shift {
+                range: 98..100,
+                by: 50 - 98,
+            },
+            Shift {
+                range: 50..98,
+                by: 52 - 50,
+            },
+        ]);
+
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(14), 14);
+        assert_eq!(map.get(99), 51);
+
+        let seeds = IntervalSet::from_iter([79..93, 55..68]);
+        assert_eq!(map.apply(&seeds).intervals(), [57..70, 81..95]);
+
+        let everything = IntervalSet::from(0..100);
+        assert_eq!(map.apply(&everything), everything);
+    }
+
+    #[test]
+    fn test_then_inverse_roundtrip() {
+        let map = PiecewiseShift::new([
+            Shift {
+                range: 98..100,
+                by: 50 - 98,
+            },
+            Shift {
+                range: 50..98,
+                by: 52 - 50,
+            },
+        ]);
+        let inverse = map.inverse().unwrap();
+
+        assert_eq!(inverse.get(50), 98);
+        assert_eq!(map.then(&inverse).unwrap().shifts(), []);
+    }
+
+    #[test]
+    fn test_inverse_non_bijective() {
+        let map = PiecewiseShift::new([Shift {
+            range: 0..5,
+            by: 10,
+        }]);
+
+        assert_eq!(map.inverse(), None);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // 0 goes to `i8::MAX`, so the images' end is one past it.
+        let to_max = PiecewiseShift::new([Shift {
+            range: 0..1,
+            by: i8::MAX,
+        }]);
+        let far = PiecewiseShift::new([Shift {
+            range: -100..-99,
+            by: 100_i8,
+        }]);
+        let further = PiecewiseShift::new([Shift {
+            range: 0..1,
+            by: 100,
+        }]);
+
+        assert_eq!(to_max.inverse(), None);
+        assert_eq!(to_max.then(&PiecewiseShift::new([])), None);
+        // Both shifts fit, but together they move -100 by 200.
+        assert_eq!(far.then(&further), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlapping")]
+    fn test_overlapping_shifts() {
+        PiecewiseShift::new([Shift { range: 0..5, by: 1 }, Shift { range: 4..6, by: 2 }]);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_from_iter_normalizes(ranges in prop::collection::vec(range(), 0..8)) {
+            let set = IntervalSet::from_iter(ranges.clone());
+            let expected: Model = ranges.into_iter().flatten().collect();
+
+            prop_assert!(is_normalized(&set));
+            prop_assert_eq!(model(&set), expected);
+        }
+
+        #[test]
+        fn prop_union(a in set(), b in set()) {
+            let union = a.union(&b);
+
+            prop_assert!(is_normalized(&union));
+            prop_assert_eq!(model(&union), &model(&a) | &model(&b));
+        }
+
+        #[test]
+        fn prop_intersection(a in set(), b in set()) {
+            let intersection = a.intersection(&b);
+
+            prop_assert!(is_normalized(&intersection));
+            prop_assert_eq!(model(&intersection), &model(&a) & &model(&b));
+        }
+
+        #[test]
+        fn prop_difference(a in set(), b in set()) {
+            let difference = a.difference(&b);
+
+            prop_assert!(is_normalized(&difference));
+            prop_assert_eq!(model(&difference), &model(&a) - &model(&b));
+        }
+
+        #[test]
+        fn prop_complement(a in set()) {
+            let complement = a.complement();
+            let universe: Model = (i8::MIN..i8::MAX).collect();
+
+            prop_assert!(is_normalized(&complement));
+            prop_assert_eq!(model(&complement), &universe - &model(&a));
+            prop_assert_eq!(complement.complement(), a);
+        }
+
+        #[test]
+        fn prop_identities(a in set(), b in set()) {
+            prop_assert_eq!(a.union(&b), b.union(&a));
+            prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+            prop_assert_eq!(a.difference(&b), a.intersection(&b.complement()));
+            prop_assert_eq!(a.difference(&b).union(&a.intersection(&b)), a);
+        }
+
+        #[test]
+        fn prop_contains(a in set(), value in any::<i8>()) {
+            prop_assert_eq!(a.contains(&value), model(&a).contains(&value));
+        }
+
+        #[test]
+        fn prop_intersects(a in set(), range in range()) {
+            prop_assert_eq!(
+                a.intersects(&range),
+                range.clone().any(|v| model(&a).contains(&v))
+            );
+        }
+
+        #[test]
+        fn prop_apply(map in piecewise_shift(), ranges in prop::collection::vec(-60..60_i8, 0..8)) {
+            let set = IntervalSet::from_iter(ranges.into_iter().map(|start| start..start + 10));
+            let applied = map.apply(&set);
+
+            let expected: Model = model(&set).into_iter().map(|v| map.get(v)).collect();
+
+            prop_assert!(is_normalized(&applied));
+            prop_assert_eq!(model(&applied), expected);
+        }
+
+        #[test]
+        fn prop_then(first in piecewise_shift(), second in piecewise_shift(), value in -70..70_i8) {
+            let composed = first.then(&second).unwrap();
+
+            prop_assert_eq!(composed.get(value), second.get(first.get(value)));
+            prop_assert!(composed.shifts().iter().all(|s| s.by != 0));
+        }
+
+        #[test]
+        fn prop_then_apply(
+            first in piecewise_shift(),
+            second in piecewise_shift(),
+            ranges in prop::collection::vec(-60..60_i8, 0..8),
+        ) {
+            let set = IntervalSet::from_iter(ranges.into_iter().map(|start| start..start + 10));
+
+            prop_assert_eq!(first.then(&second).unwrap().apply(&set), second.apply(&first.apply(&set)));
+        }
+
+        #[test]
+        fn prop_inverse(map in piecewise_shift(), value in -70..70_i8) {
+            match map.inverse() {
+                Some(inverse) => {
+                    prop_assert_eq!(inverse.get(map.get(value)), value);
+                    prop_assert_eq!(map.get(inverse.get(value)), value);
+                }
+                None => {
+                    // Not a bijection: some image is hit twice.
+                    let images: Model = (-100..=120_i8).map(|v| map.get(v)).collect();
+                    prop_assert!(images.len() < (-100..=120_i8).count());
+                }
+            }
+        }
+
+        #[test]
+        fn prop_inverse_of_permutation(
+            (start, lengths) in (-60..0_i8, prop::collection::vec(1..10_i8, 1..5)),
+            rotation in 0..5_usize,
+        ) {
+            // Cut a range into adjacent blocks and rotate their order, like the almanac
+            // maps do.
+            let mut blocks = Vec::new();
+            let mut cursor = start;
+            for length in lengths {
+                blocks.push(cursor..cursor + length);
+                cursor += length;
+            }
+
+            let mut destinations = blocks.clone();
+            destinations.rotate_left(rotation % blocks.len());
+
+            let mut target = start;
+            let map = PiecewiseShift::new(destinations.into_iter().map(|block| {
+                let shift = Shift { range: block.clone(), by: target - block.start };
+                target += block.end - block.start;
+                shift
+            }));
+
+            let inverse = map.inverse().expect("permutation is a bijection");
+            for value in start - 5..cursor + 5 {
+                prop_assert_eq!(inverse.get(map.get(value)), value);
+            }
+        }
+
+        #[test]
+        fn prop_get(map in piecewise_shift(), value in -70..70_i8) {
+            let expected = map
+                .shifts()
+                .iter()
+                .find(|s| s.range.contains(&value))
+                .map_or(value, |s| value + s.by);
+
+            prop_assert_eq!(map.get(value), expected);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod sparse;
+
+pub trait Challenge {
+    fn input() -> &'static str
+    where
+        Self: Sized;
+
+    fn solve(&self) -> String;
+
+    fn solution(&self) -> &'static str;
+
+    fn day(&self) -> u8;
+
+    fn part(&self) -> u8;
+
+    fn title(&self) -> String {
+        format!("Day {} / Part {}", self.day(), self.part())
+    }
+}
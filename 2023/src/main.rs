@@ -1,35 +1,80 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod iter;
-
-trait Challenge {
-    fn input() -> &'static str
-    where
-        Self: Sized;
-
-    fn solve(&self) -> String;
-
-    fn solution(&self) -> &'static str;
-
-    fn day(&self) -> u8;
-
-    fn part(&self) -> u8;
-
-    fn title(&self) -> String {
-        format!("Day {} / Part {}", self.day(), self.part())
+use aoc2023::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, grid::Grid,
+    sparse::SparseGrid, Challenge,
+};
+
+const USAGE: &str = "\
+Usage: aoc2023 [--expansion <FACTOR> | --bag [CONFIDENCE] | --pipes]
+
+Without arguments, solves and checks all challenges.
+
+Options:
+  --expansion <FACTOR>  Sum of day 11 galaxy distances, with empty space expanded
+                        FACTOR times instead
+  --bag [CONFIDENCE]    Estimate the day 2 bag from all games, with intervals at
+                        CONFIDENCE (default: 0.95)
+  --pipes               Draw the day 10 pipe map, with the loop and the tiles it
+                        encloses highlighted (in colour on terminals)";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    match &args[..] {
+        [] => run_all(),
+        [flag, factor] if flag == "--expansion" => match factor.parse() {
+            Ok(factor) => expansion(factor),
+            Err(err) => usage(&format!("invalid expansion factor {factor:?}: {err}")),
+        },
+        [flag] if flag == "--bag" => bag(0.95),
+        [flag, confidence] if flag == "--bag" => match confidence.parse() {
+            Ok(confidence) if 0.0 < confidence && confidence < 1.0 => bag(confidence),
+            _ => usage(&format!(
+                "invalid confidence {confidence:?}: must be between 0 and 1"
+            )),
+        },
+        [flag] if flag == "--pipes" => pipes(),
+        [flag] if flag == "--help" || flag == "-h" => println!("{USAGE}"),
+        _ => usage(&format!("unexpected arguments {args:?}")),
     }
 }
 
-fn main() {
+fn usage(error: &str) -> ! {
+    eprintln!("error: {error}\n\n{USAGE}");
+    std::process::exit(2);
+}
+
+/// Day 11, but for any expansion factor.
+fn expansion(factor: u64) {
+    let image = SparseGrid::parse(day11::part1::Part::input(), b'#');
+
+    match day11::galaxies::expanded_distances(&image, factor) {
+        Some(sum) => println!("Day 11, expanded {factor} times: {sum}"),
+        None => {
+            eprintln!("error: expanding {factor} times overflows coordinates");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Day 2, working backwards from the games to the bag.
+fn bag(confidence: f64) {
+    let games = day02::game::parse_games(day02::part1::Part::input()).unwrap();
+
+    println!("{}", day02::inference::infer(&games, confidence));
+}
+
+/// Day 10, drawn.
+fn pipes() {
+    let map: Grid<char> = day10::part1::Part::input().parse().unwrap();
+    let pipe_loop = day10::pipes::PipeLoop::find(&map).unwrap();
+
+    if let Err(err) = day10::render::print(&map, &pipe_loop) {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
+
+fn run_all() {
     let challenges: Vec<Box<dyn Challenge>> = vec![
         Box::new(day01::part1::Part {}),
         Box::new(day01::part2::Part {}),
@@ -50,6 +95,7 @@ fn main() {
         Box::new(day09::part1::Part {}),
         Box::new(day09::part2::Part {}),
         Box::new(day10::part1::Part {}),
+        Box::new(day10::part2::Part {}),
         Box::new(day11::part1::Part {}),
         Box::new(day11::part2::Part {}),
     ];
@@ -0,0 +1,206 @@
+use crate::geom::Point;
+
+/// Points scattered over a mostly empty grid, plus which rows and columns hold any.
+///
+/// Keeps only the points themselves, and two bitsets of occupied rows and columns. For
+/// inputs like galaxy images, that's a fraction of a full [`Grid`](crate::grid::Grid).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid {
+    width: usize,
+    height: usize,
+    points: Vec<Point<usize>>,
+    occupied_rows: BitSet,
+    occupied_columns: BitSet,
+}
+
+impl SparseGrid {
+    /// Panics if any point lies outside `width` and `height`.
+    pub fn new(width: usize, height: usize, points: Vec<Point<usize>>) -> Self {
+        let mut occupied_rows = BitSet::new(height);
+        let mut occupied_columns = BitSet::new(width);
+
+        for point in &points {
+            occupied_rows.insert(point.y);
+            occupied_columns.insert(point.x);
+        }
+
+        Self {
+            width,
+            height,
+            points,
+            occupied_rows,
+            occupied_columns,
+        }
+    }
+
+    /// Every `occupied` byte of `input` is a point. Lines may differ in length; the
+    /// grid is as wide as the longest one.
+    pub fn parse(input: &str, occupied: u8) -> Self {
+        let mut points = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.as_bytes();
+
+            // Scanning bytes is much faster than going through `char`s.
+            points.extend(
+                line.iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| b == occupied)
+                    .map(|(j, _)| Point::new(j, i)),
+            );
+
+            width = width.max(line.len());
+            height = i + 1;
+        }
+
+        Self::new(width, height, points)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn points(&self) -> &[Point<usize>] {
+        &self.points
+    }
+
+    pub fn empty_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.height).filter(|&i| !self.occupied_rows.contains(i))
+    }
+
+    pub fn empty_columns(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.width).filter(|&j| !self.occupied_columns.contains(j))
+    }
+
+    /// All points, after every empty row and column has grown into `factor` of them.
+    /// `None` if coordinates overflow `u64`.
+    ///
+    /// Lookup tables map every row and column to its expanded coordinate, built from
+    /// prefix counts of empty ones. Points are then mapped in a single pass, instead of
+    /// shifting all of them once per empty row or column.
+    pub fn expand(&self, factor: u64) -> Option<Vec<Point<u64>>> {
+        let rows = offsets(&self.occupied_rows, factor)?;
+        let columns = offsets(&self.occupied_columns, factor)?;
+
+        Some(
+            self.points
+                .iter()
+                .map(|point| Point::new(columns[point.x], rows[point.y]))
+                .collect(),
+        )
+    }
+}
+
+/// Expanded coordinate for every index: the index itself, plus `factor - 1` for every
+/// empty one before it.
+fn offsets(occupied: &BitSet, factor: u64) -> Option<Vec<u64>> {
+    let mut empty: u64 = 0;
+
+    (0..occupied.len())
+        .map(|index| {
+            let expanded = (index as u64 - empty).checked_add(empty.checked_mul(factor)?);
+            empty += !occupied.contains(index) as u64;
+
+            expanded
+        })
+        .collect()
+}
+
+/// Fixed-size set of small integers, one bit each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn insert(&mut self, i: usize) {
+        assert!(i < self.len, "{i} out of bounds for {} bits", self.len);
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const IMAGE: &str = "\
+#..
+...
+..#
+.#.";
+
+    #[test]
+    fn test_parse() {
+        let grid = SparseGrid::parse(IMAGE, b'#');
+
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(
+            grid.points(),
+            [Point::new(0, 0), Point::new(2, 2), Point::new(1, 3)]
+        );
+        assert_eq!(grid.empty_rows().collect::<Vec<_>>(), [1]);
+        assert_eq!(grid.empty_columns().count(), 0);
+    }
+
+    #[rstest]
+    #[case(0, [(0, 0), (1, 1), (2, 2)])]
+    #[case(1, [(0, 0), (3, 2), (4, 4)])]
+    #[case(10, [(0, 0), (21, 11), (22, 22)])]
+    fn test_expand(#[case] factor: u64, #[case] expected: [(u64, u64); 3]) {
+        // Empty: rows 1 and 3, columns 1 and 2.
+        let grid = SparseGrid::new(
+            5,
+            5,
+            vec![Point::new(0, 0), Point::new(3, 2), Point::new(4, 4)],
+        );
+
+        assert_eq!(
+            grid.expand(factor).unwrap(),
+            expected.map(|(x, y)| Point::new(x, y))
+        );
+    }
+
+    #[test]
+    fn test_expand_overflow() {
+        let grid = SparseGrid::parse(IMAGE, b'#');
+
+        assert_eq!(grid.expand(u64::MAX), None);
+        assert!(grid.expand(u64::MAX / 2).is_some());
+    }
+
+    #[test]
+    fn test_bitset() {
+        let mut set = BitSet::new(130);
+        for i in [0, 63, 64, 129] {
+            set.insert(i);
+        }
+
+        assert_eq!(
+            (0..130).filter(|&i| set.contains(i)).collect::<Vec<_>>(),
+            [0, 63, 64, 129]
+        );
+    }
+}